@@ -17,7 +17,8 @@ TW_EXTERN_C_BEGIN
 ///
 /// Bitcoin Core and some other wallets support a message signing & verification format, to create a proof (a signature)
 /// that someone has access to the private keys of a specific address.
-/// This feature currently works on old legacy addresses only.
+/// Signing works on old legacy addresses only; verification also supports native segwit (P2WPKH) addresses,
+/// with recoverable (BIP137) or BIP322 simple signatures.
 TW_EXPORT_STRUCT
 struct TWBitcoinMessageSigner;
 
@@ -33,7 +34,7 @@ TWString* _Nonnull TWBitcoinMessageSignerSignMessage(const struct TWPrivateKey*
 
 /// Verify signature for a message.
 ///
-/// \param address: address to use, only legacy is supported
+/// \param address: address to use, legacy (P2PKH, P2SH-P2WPKH) or native segwit (P2WPKH)
 /// \param message: the message signed (without prefix)
 /// \param signature: in Base64-encoded form.
 /// \returns false on any invalid input (does not throw).
@@ -0,0 +1,88 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+#pragma once
+
+#include "TWBase.h"
+#include "TWCoinType.h"
+#include "TWString.h"
+
+TW_EXTERN_C_BEGIN
+
+/// BIP21 payment request URI, e.g. `bitcoin:<address>?amount=0.001&label=<label>`.
+TW_EXPORT_CLASS
+struct TWBitcoinPaymentURI;
+
+/// Parses a payment URI.  The scheme must be the one of the coin (e.g. "litecoin").
+///
+/// \param uri the URI string
+/// \param coin coin type, used to check the scheme and the address
+/// \note Must be deleted with \TWBitcoinPaymentURIDelete
+/// \return the parsed URI, or nullptr if it is invalid
+TW_EXPORT_STATIC_METHOD
+struct TWBitcoinPaymentURI* _Nullable TWBitcoinPaymentURICreateWithString(TWString* _Nonnull uri, enum TWCoinType coin);
+
+/// Creates a payment URI.
+///
+/// \param address destination address, valid for the coin
+/// \param amount requested amount in satoshis, 0 for none, at most the coin's total supply limit
+/// \param label label for the address, may be empty
+/// \param message message describing the payment, may be empty
+/// \param coin coin type
+/// \note Must be deleted with \TWBitcoinPaymentURIDelete
+/// \return the URI, or nullptr if the address or amount is invalid, or the coin has no URI scheme
+TW_EXPORT_STATIC_METHOD
+struct TWBitcoinPaymentURI* _Nullable TWBitcoinPaymentURICreate(TWString* _Nonnull address, int64_t amount, TWString* _Nonnull label, TWString* _Nonnull message, enum TWCoinType coin);
+
+/// Deletes a payment URI.
+///
+/// \param uri URI to delete
+TW_EXPORT_METHOD
+void TWBitcoinPaymentURIDelete(struct TWBitcoinPaymentURI* _Nonnull uri);
+
+/// Returns the URI string, with percent-encoded parameter values.
+///
+/// \param uri Non-null pointer to a payment URI
+TW_EXPORT_PROPERTY
+TWString* _Nonnull TWBitcoinPaymentURIDescription(const struct TWBitcoinPaymentURI* _Nonnull uri);
+
+/// Returns the destination address; empty if there is only a lightning invoice.
+///
+/// \param uri Non-null pointer to a payment URI
+TW_EXPORT_PROPERTY
+TWString* _Nonnull TWBitcoinPaymentURIAddress(const struct TWBitcoinPaymentURI* _Nonnull uri);
+
+/// Returns the requested amount in satoshis, 0 if not specified.
+///
+/// \param uri Non-null pointer to a payment URI
+TW_EXPORT_PROPERTY
+int64_t TWBitcoinPaymentURIAmount(const struct TWBitcoinPaymentURI* _Nonnull uri);
+
+/// Returns the label for the address.
+///
+/// \param uri Non-null pointer to a payment URI
+TW_EXPORT_PROPERTY
+TWString* _Nonnull TWBitcoinPaymentURILabel(const struct TWBitcoinPaymentURI* _Nonnull uri);
+
+/// Returns the message describing the payment.
+///
+/// \param uri Non-null pointer to a payment URI
+TW_EXPORT_PROPERTY
+TWString* _Nonnull TWBitcoinPaymentURIMessage(const struct TWBitcoinPaymentURI* _Nonnull uri);
+
+/// Returns the BOLT11 lightning invoice (`lightning` parameter), if any.
+///
+/// \param uri Non-null pointer to a payment URI
+TW_EXPORT_PROPERTY
+TWString* _Nonnull TWBitcoinPaymentURILightning(const struct TWBitcoinPaymentURI* _Nonnull uri);
+
+/// Returns the PayJoin (BIP78) endpoint (`pj` parameter), if any.
+///
+/// \param uri Non-null pointer to a payment URI
+TW_EXPORT_PROPERTY
+TWString* _Nonnull TWBitcoinPaymentURIPayjoin(const struct TWBitcoinPaymentURI* _Nonnull uri);
+
+TW_EXTERN_C_END
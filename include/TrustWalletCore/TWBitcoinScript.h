@@ -11,6 +11,7 @@
 #include "TWCoinType.h"
 #include "TWData.h"
 #include "TWPublicKey.h"
+#include "TWString.h"
 
 TW_EXTERN_C_BEGIN
 
@@ -153,6 +154,20 @@ TWData* _Nullable TWBitcoinScriptMatchPayToWitnessScriptHash(const struct TWBitc
 TW_EXPORT_METHOD
 TWData* _Nonnull TWBitcoinScriptEncode(const struct TWBitcoinScript* _Nonnull script);
 
+/// Disassembles the script, in the format of Bitcoin Core's asm, e.g. "OP_DUP OP_HASH160 <hex> OP_EQUALVERIFY OP_CHECKSIG".
+///
+/// \param script Non-null pointer to a script
+/// \return The opcodes, separated by spaces
+TW_EXPORT_METHOD
+TWString* _Nonnull TWBitcoinScriptDisassemble(const struct TWBitcoinScript* _Nonnull script);
+
+/// Human-readable description of the script, e.g. "P2WPKH", "2-of-3 CHECKMULTISIG", "CSV 144 blocks".
+///
+/// \param script Non-null pointer to a script
+/// \return The description, empty if the script is not recognized
+TW_EXPORT_METHOD
+TWString* _Nonnull TWBitcoinScriptExplain(const struct TWBitcoinScript* _Nonnull script);
+
 /// Builds a standard 'pay to public key' script.
 ///
 /// \param pubkey Non-null pointer to a pubkey
@@ -0,0 +1,28 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+#pragma once
+
+#include "TWBase.h"
+#include "TWData.h"
+
+TW_EXTERN_C_BEGIN
+
+/// Bitcoin payment split into several independent transactions (merge avoidance).
+///
+/// UTXOs are grouped by their address, and each transaction spends from one address only, with its own change address,
+/// so that the sender's addresses are not linked together.  Each resulting plan is signed separately with AnySigner.
+TW_EXPORT_STRUCT
+struct TWBitcoinSplitPayment;
+
+/// Plans a split payment.
+///
+/// \param input: serialized Bitcoin.Proto.SigningInput, with one `split_change_addresses` entry for each possible transaction
+/// \returns serialized Bitcoin.Proto.SplitPaymentPlans, or empty data if the input cannot be parsed.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWBitcoinSplitPaymentPlan(TWData* _Nonnull input);
+
+TW_EXTERN_C_END
@@ -10,6 +10,7 @@
 #include "TWCoinType.h"
 #include "TWCurve.h"
 #include "TWData.h"
+#include "TWDataVector.h"
 #include "TWDerivation.h"
 #include "TWDerivationPath.h"
 #include "TWHDVersion.h"
@@ -261,4 +262,31 @@ TWString* _Nonnull TWHDWalletGetExtendedPublicKeyDerivation(struct TWHDWallet* _
 TW_EXPORT_STATIC_METHOD
 struct TWPublicKey* _Nullable TWHDWalletGetPublicKeyFromExtended(TWString* _Nonnull extended, enum TWCoinType coin, TWString* _Nonnull derivationPath);
 
+/// Computes the public keys of a range of addresses (change/startIndex ... change/startIndex+count-1) from an account
+/// extended public key, faster than one by one (larger ranges on multiple threads).  Intended for account discovery.
+///
+/// \param extended extended public key of the account
+/// \param coin a coin type
+/// \param change change level index, 0 for receive and 1 for change addresses
+/// \param startIndex index of the first address
+/// \param count number of public keys, at most 10000
+/// \note Returned object needs to be deleted with \TWDataVectorDelete
+/// \return the public keys, in order; empty on error
+TW_EXPORT_STATIC_METHOD
+struct TWDataVector* _Nonnull TWHDWalletGetPublicKeysFromExtended(TWString* _Nonnull extended, enum TWCoinType coin, uint32_t change, uint32_t startIndex, uint32_t count);
+
+/// Computes the locking scripts (scriptPubKey) of a range of addresses (change/startIndex ... change/startIndex+count-1)
+/// from an account extended public key, ready for scanning the chain.  Only for coins with Bitcoin-style scripts.
+///
+/// \param extended extended public key of the account
+/// \param coin a coin type
+/// \param derivation address derivation, e.g. TWDerivationBitcoinLegacy for P2PKH scripts
+/// \param change change level index, 0 for receive and 1 for change addresses
+/// \param startIndex index of the first address
+/// \param count number of scripts, at most 10000
+/// \note Returned object needs to be deleted with \TWDataVectorDelete
+/// \return the locking scripts, in order; empty on error
+TW_EXPORT_STATIC_METHOD
+struct TWDataVector* _Nonnull TWHDWalletGetLockScriptsFromExtended(TWString* _Nonnull extended, enum TWCoinType coin, enum TWDerivation derivation, uint32_t change, uint32_t startIndex, uint32_t count);
+
 TW_EXTERN_C_END
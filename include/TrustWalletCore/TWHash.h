@@ -8,6 +8,7 @@
 
 #include "TWBase.h"
 #include "TWData.h"
+#include "TWString.h"
 
 TW_EXTERN_C_BEGIN
 
@@ -36,6 +37,14 @@ TWData *_Nonnull TWHashSHA1(TWData *_Nonnull data);
 TW_EXPORT_STATIC_METHOD
 TWData *_Nonnull TWHashSHA256(TWData *_Nonnull data);
 
+/// Computes the BIP340 tagged hash of a block of data: SHA256(SHA256(tag) || SHA256(tag) || data), as used by Taproot.
+///
+/// \param tag Non-null tag, e.g. "TapLeaf"
+/// \param data Non-null block of data
+/// \return Non-null computed tagged hash
+TW_EXPORT_STATIC_METHOD
+TWData *_Nonnull TWHashSHA256Tagged(TWString *_Nonnull tag, TWData *_Nonnull data);
+
 /// Computes the SHA512 of a block of data.
 ///
 /// \param data Non-null block of data
@@ -199,6 +199,36 @@ std::vector<TypeWithAmount> InputSelector<TypeWithAmount>::selectSimple(int64_t
     return {};
 }
 
+template <typename TypeWithAmount>
+std::vector<TypeWithAmount> InputSelector<TypeWithAmount>::selectInOrder(int64_t targetValue,
+                                                                         int64_t byteFee,
+                                                                         int64_t numOutputs) {
+    // if target value is zero, no UTXOs are needed
+    if (targetValue == 0) {
+        return {};
+    }
+
+    const int64_t dustThreshold = feeCalculator.calculateSingleInput(byteFee);
+
+    // Go through inputs in a single pass, in the order they appear
+    int64_t sum = 0;
+    std::vector<TypeWithAmount> selected;
+    for (auto& input : _inputs) {
+        if (input.amount <= dustThreshold) {
+            continue; // skip dust
+        }
+        selected.push_back(input);
+        sum += input.amount;
+        if (sum >= targetValue + feeCalculator.calculate(selected.size(), numOutputs, byteFee)) {
+            // we have enough
+            return selected;
+        }
+    }
+
+    // not enough
+    return {};
+}
+
 template <typename TypeWithAmount>
 std::vector<TypeWithAmount>
 InputSelector<TypeWithAmount>::selectMaxAmount(int64_t byteFee) noexcept {
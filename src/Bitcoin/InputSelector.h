@@ -32,6 +32,14 @@ public:
     std::vector<TypeWithAmount> selectSimple(int64_t targetValue, int64_t byteFee,
                                              int64_t numOutputs = 2);
 
+    /// Selects unspent transactions to use given a target transaction value, taking inputs
+    /// in the order they are given (e.g. by preference) until the target and the fee are covered.
+    /// Dust inputs are skipped.
+    ///
+    /// \returns the list of selected inputs, or an empty list if there are insufficient funds.
+    std::vector<TypeWithAmount> selectInOrder(int64_t targetValue, int64_t byteFee,
+                                              int64_t numOutputs = 2);
+
     /// Selects UTXOs for max amount; select all except those which would reduce output (dust).
     /// Return indices. One output and no change is assumed.
     std::vector<TypeWithAmount> selectMaxAmount(int64_t byteFee) noexcept;
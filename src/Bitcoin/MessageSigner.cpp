@@ -6,6 +6,8 @@
 
 #include "MessageSigner.h"
 #include "Address.h"
+#include "SegwitAddress.h"
+#include "Transaction.h"
 
 #include "Base64.h"
 #include "BinaryCoding.h"
@@ -63,22 +65,78 @@ std::string MessageSigner::signMessage(const PrivateKey& privateKey, const std::
     return Base64::encode(sigAdjusted);
 }
 
-std::string MessageSigner::recoverAddressFromMessage(const std::string& message, const Data& signature) {
-    if (signature.size() < SignatureRSVLength) {
+Data MessageSigner::messageToBip322Hash(const std::string& message) {
+    return Hash::sha256Tagged(Bip322Tag, TW::data(message));
+}
+
+// Recover the public key from a recoverable signature; the header byte also tells if the key is compressed. May throw.
+PublicKey recoverPublicKeyFromMessage(const std::string& message, const Data& signature, bool& compressed) {
+    if (signature.size() < MessageSigner::SignatureRSVLength) {
         throw std::invalid_argument("signature too short");
     }
     const auto messageHash = MessageSigner::messageToHash(message);
     auto recId = signature[0];
-    auto compressed = false;
-    if (recId >= PublicKey::SignatureVOffset + 4) {
-        recId -= 4;
+    compressed = false;
+    if (recId >= MessageSigner::VOffset + 4 && recId < MessageSigner::VOffsetMax) {
+        // all types other than uncompressed P2PKH use compressed keys
+        recId = MessageSigner::VOffset + (recId - MessageSigner::VOffset) % 4;
         compressed = true;
     }
     if (recId >= PublicKey::SignatureVOffset) {
         recId -= PublicKey::SignatureVOffset;
     }
+    return PublicKey::recoverRaw(TW::subData(signature, 1), recId, messageHash);
+}
 
-    const auto publicKeyRecovered = PublicKey::recoverRaw(TW::subData(signature, 1), recId, messageHash);
+// Verify a BIP322 simple signature for a P2WPKH address: the witness of the virtual `to_sign` transaction. May throw.
+bool verifyBip322Simple(const Data& keyHash, const std::string& message, const Data& signature) {
+    // witness stack: <signature> <public key>
+    size_t index = 0;
+    const auto [countOk, count] = decodeVarInt(signature, index);
+    if (!countOk || count != 2) {
+        return false;
+    }
+    std::vector<Data> witness;
+    for (auto i = 0; i < 2; ++i) {
+        const auto [sizeOk, size] = decodeVarInt(signature, index);
+        if (!sizeOk || size > signature.size() - index) {
+            return false;
+        }
+        witness.push_back(TW::subData(signature, index, size));
+        index += size;
+    }
+    const auto& derSignature = witness[0];
+    const auto& publicKeyData = witness[1];
+    if (index != signature.size() || derSignature.empty() || !PublicKey::isValid(publicKeyData, TWPublicKeyTypeSECP256k1)) {
+        return false;
+    }
+    if (Hash::sha256ripemd(publicKeyData.data(), publicKeyData.size()) != keyHash) {
+        return false;
+    }
+
+    // to_spend: commits to the message, paid to the address
+    Data commitment{OP_0, static_cast<byte>(MessageSigner::DigestLength)};
+    TW::append(commitment, MessageSigner::messageToBip322Hash(message));
+    Transaction toSpend(0);
+    toSpend.inputs.emplace_back(OutPoint(Data(32), 0xffffffff), Script(commitment), 0);
+    toSpend.outputs.emplace_back(0, Script::buildPayToWitnessPublicKeyHash(keyHash));
+    Data toSpendData;
+    toSpend.encode(toSpendData, Transaction::NonSegwit);
+    const auto toSpendId = Hash::sha256d(toSpendData.data(), toSpendData.size());
+
+    // to_sign: spends to_spend, signed as a regular P2WPKH input
+    Transaction toSign(0);
+    toSign.inputs.emplace_back(OutPoint(toSpendId, 0), Script(), 0);
+    toSign.outputs.emplace_back(0, Script(Data{OP_RETURN}));
+    const auto hashType = static_cast<TWBitcoinSigHashType>(derSignature.back());
+    const auto sighash = toSign.getSignatureHash(Script::buildPayToPublicKeyHash(keyHash), 0, hashType, 0, WITNESS_V0);
+
+    return PublicKey(publicKeyData, TWPublicKeyTypeSECP256k1).verifyAsDER(TW::subData(derSignature, 0, derSignature.size() - 1), sighash);
+}
+
+std::string MessageSigner::recoverAddressFromMessage(const std::string& message, const Data& signature) {
+    auto compressed = false;
+    const auto publicKeyRecovered = recoverPublicKeyFromMessage(message, signature, compressed);
 
     if (!compressed) {
         // uncompressed public key
@@ -101,11 +159,38 @@ bool MessageSigner::verifyMessage(const std::string& address, const std::string&
 
 /// May throw
 bool MessageSigner::verifyMessage(const std::string& address, const std::string& message, const Data& signature) {
-    if (!Bitcoin::Address::isValid(address)) {
-        throw std::invalid_argument("Input address invalid, must be valid legacy");
+    if (Bitcoin::Address::isValid(address)) {
+        const auto addressRecovered = recoverAddressFromMessage(message, signature);
+        if (addressRecovered == address) {
+            return true;
+        }
+        if (Bitcoin::Address(address).bytes[0] != TW::p2shPrefix(TWCoinTypeBitcoin)) {
+            return false;
+        }
+        // P2SH-P2WPKH: the script hash of the witness program of the recovered key
+        auto compressed = false;
+        const auto publicKey = recoverPublicKeyFromMessage(message, signature, compressed).compressed();
+        const auto redeemScript = Script::buildPayToWitnessPublicKeyHash(publicKey.hash({}));
+        auto scriptHash = Data{TW::p2shPrefix(TWCoinTypeBitcoin)};
+        TW::append(scriptHash, redeemScript.hash());
+        return compressed && Bitcoin::Address(scriptHash).string() == address;
+    }
+
+    const auto [segwitAddress, hrp, valid] = SegwitAddress::decode(address);
+    if (!valid) {
+        throw std::invalid_argument("Input address invalid, must be valid legacy or segwit");
+    }
+    if (segwitAddress.witnessVersion != 0 || segwitAddress.witnessProgram.size() != 20) {
+        throw std::invalid_argument("Only P2WPKH segwit addresses are supported");
+    }
+    const auto& keyHash = segwitAddress.witnessProgram;
+    if (signature.size() == SignatureRSVLength && signature[0] >= VOffset && signature[0] < VOffsetMax) {
+        // recoverable signature
+        auto compressed = false;
+        const auto publicKey = recoverPublicKeyFromMessage(message, signature, compressed);
+        return compressed && publicKey.compressed().hash({}) == keyHash;
     }
-    const auto addressRecovered = recoverAddressFromMessage(message, signature);
-    return (addressRecovered == address);
+    return verifyBip322Simple(keyHash, message, signature);
 }
 
 } // namespace TW::Bitcoin
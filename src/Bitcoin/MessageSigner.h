@@ -17,7 +17,8 @@ namespace TW::Bitcoin {
 ///
 /// Bitcoin Core and some other wallets support a message signing & verification format, to create a proof (a signature)
 /// that someone has access to the private keys of a specific address.
-/// This feature currently works on old legacy addresses only.
+/// Signing works on old legacy addresses only.  Verification also supports native segwit (P2WPKH) addresses,
+/// with either a recovery-based signature (BIP137 / Electrum style) or a BIP322 "simple" signature.
 class MessageSigner {
   public:
     /// Sign a message.
@@ -32,14 +33,14 @@ class MessageSigner {
     static std::string signMessage(const PrivateKey& privateKey, const std::string& address, const std::string& message, bool compressed = true);
   
     /// Verify signature for a message.
-    /// address: address to use, only legacy is supported
+    /// address: address to use, legacy (P2PKH, or P2SH-P2WPKH with BIP137 signature) or P2WPKH
     /// message: the message signed (without prefix)
-    /// signature: in Base64-encoded form.
+    /// signature: in Base64-encoded form, 65-byte recoverable signature, or BIP322 simple signature (P2WPKH only).
     /// Returns false on any invalid input (does not throw).
     static bool verifyMessage(const std::string& address, const std::string& message, const std::string& signature) noexcept;
 
     /// Verify signature for a message.
-    /// Address: address to use, only legacy is supported
+    /// Address: address to use, legacy or P2WPKH
     /// message: the message signed (without prefix)
     /// signature: in binary form.
     /// May throw
@@ -51,11 +52,17 @@ class MessageSigner {
     /// Append prefix and compute hash for a message
     static Data messageToHash(const std::string& message);
 
+    /// Compute the BIP322 message hash (tagged hash)
+    static Data messageToBip322Hash(const std::string& message);
+
     static constexpr auto MessagePrefix = "Bitcoin Signed Message:\n";
+    static constexpr auto Bip322Tag = "BIP0322-signed-message";
     static const byte DigestLength = 32;
     static const byte SignatureRSLength = 64;
     static constexpr byte SignatureRSVLength = SignatureRSLength + 1;
     static const byte VOffset = 27;
+    /// BIP137 header byte ranges above VOffset: +0 uncompressed P2PKH, +4 compressed P2PKH, +8 P2SH-P2WPKH, +12 P2WPKH
+    static const byte VOffsetMax = VOffset + 16;
 };
 
 } // namespace TW::Bitcoin
@@ -134,7 +134,8 @@ enum OpCode {
    OP_NOP2 [[maybe_unused]] = OP_CHECKLOCKTIMEVERIFY,
    OP_CHECKSEQUENCEVERIFY = 0xb2,
    OP_NOP3 [[maybe_unused]] = OP_CHECKSEQUENCEVERIFY,
-   OP_NOP4 [[maybe_unused]] = 0xb3,
+   OP_CHECKTEMPLATEVERIFY = 0xb3,
+   OP_NOP4 [[maybe_unused]] = OP_CHECKTEMPLATEVERIFY,
    OP_NOP5 [[maybe_unused]] = 0xb4,
    OP_NOP6 [[maybe_unused]] = 0xb5,
    OP_NOP7 [[maybe_unused]] = 0xb6,
@@ -0,0 +1,263 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+#include "PaymentURI.h"
+#include "Script.h"
+
+#include <algorithm>
+#include <cctype>
+#include <limits>
+
+namespace TW::Bitcoin {
+
+namespace {
+
+const Amount SatoshisPerCoin = 100'000'000;
+
+std::string toLower(std::string str) {
+    std::transform(str.begin(), str.end(), str.begin(), [](unsigned char c) { return static_cast<char>(std::tolower(c)); });
+    return str;
+}
+
+int hexDigitValue(char c) {
+    if (c >= '0' && c <= '9') {
+        return c - '0';
+    }
+    if (c >= 'a' && c <= 'f') {
+        return c - 'a' + 10;
+    }
+    if (c >= 'A' && c <= 'F') {
+        return c - 'A' + 10;
+    }
+    return -1;
+}
+
+std::optional<std::string> percentDecode(const std::string& str) {
+    std::string result;
+    for (auto i = 0ul; i < str.size(); ++i) {
+        if (str[i] != '%') {
+            result.push_back(str[i]);
+            continue;
+        }
+        if (i + 2 >= str.size()) {
+            return std::nullopt;
+        }
+        const auto high = hexDigitValue(str[i + 1]);
+        const auto low = hexDigitValue(str[i + 2]);
+        if (high < 0 || low < 0) {
+            return std::nullopt;
+        }
+        result.push_back(static_cast<char>(high * 16 + low));
+        i += 2;
+    }
+    return result;
+}
+
+std::string percentEncode(const std::string& str) {
+    static const char* digits = "0123456789ABCDEF";
+    std::string result;
+    for (unsigned char c : str) {
+        if (std::isalnum(c) || c == '-' || c == '.' || c == '_' || c == '~') {
+            result.push_back(static_cast<char>(c));
+        } else {
+            result.push_back('%');
+            result.push_back(digits[c >> 4]);
+            result.push_back(digits[c & 0x0f]);
+        }
+    }
+    return result;
+}
+
+} // namespace
+
+std::optional<Amount> PaymentURI::parseAmount(const std::string& amount, TWCoinType coin) {
+    const auto dot = amount.find('.');
+    const auto whole = amount.substr(0, dot);
+    const auto fraction = dot == std::string::npos ? std::string() : amount.substr(dot + 1);
+    const auto isDigits = [](const std::string& s) { return std::all_of(s.begin(), s.end(), [](unsigned char c) { return std::isdigit(c); }); };
+    if ((whole.empty() && fraction.empty()) || !isDigits(whole) || !isDigits(fraction) || fraction.size() > 8) {
+        return std::nullopt;
+    }
+    const auto max = maxAmount(coin);
+    // check while accumulating, so that long inputs cannot overflow
+    Amount result = 0;
+    for (auto c : whole) {
+        result = result * 10 + (c - '0');
+        if (result > max / SatoshisPerCoin) {
+            return std::nullopt;
+        }
+    }
+    Amount fractionValue = 0;
+    for (auto i = 0ul; i < 8; ++i) {
+        fractionValue = fractionValue * 10 + (i < fraction.size() ? fraction[i] - '0' : 0);
+    }
+    result *= SatoshisPerCoin;
+    if (fractionValue > max - result) {
+        return std::nullopt;
+    }
+    return result + fractionValue;
+}
+
+Amount PaymentURI::maxAmount(TWCoinType coin) {
+    // MAX_MONEY of the reference implementations
+    switch (coin) {
+    case TWCoinTypeBitcoin:
+    case TWCoinTypeBitcoinCash:
+    case TWCoinTypeDash:
+    case TWCoinTypeZcash:
+        return 21'000'000 * SatoshisPerCoin;
+    case TWCoinTypeLitecoin:
+        return 84'000'000 * SatoshisPerCoin;
+    case TWCoinTypeDogecoin:
+        return 10'000'000'000 * SatoshisPerCoin;
+    default:
+        return std::numeric_limits<Amount>::max();
+    }
+}
+
+std::string PaymentURI::formatAmount(Amount amount) {
+    auto result = std::to_string(amount / SatoshisPerCoin);
+    auto fraction = std::to_string(amount % SatoshisPerCoin);
+    fraction.insert(0, 8 - fraction.size(), '0');
+    fraction.erase(fraction.find_last_not_of('0') + 1);
+    if (!fraction.empty()) {
+        result += "." + fraction;
+    }
+    return result;
+}
+
+std::string PaymentURI::schemeForCoin(TWCoinType coin) {
+    switch (coin) {
+    case TWCoinTypeBitcoin:
+        return "bitcoin";
+    case TWCoinTypeBitcoinCash:
+        return "bitcoincash";
+    case TWCoinTypeDash:
+        return "dash";
+    case TWCoinTypeDogecoin:
+        return "dogecoin";
+    case TWCoinTypeLitecoin:
+        return "litecoin";
+    case TWCoinTypeZcash:
+        return "zcash";
+    default:
+        return "";
+    }
+}
+
+std::optional<PaymentURI> PaymentURI::parse(const std::string& uri, TWCoinType coin) {
+    const auto colon = uri.find(':');
+    if (colon == std::string::npos || colon == 0) {
+        return std::nullopt;
+    }
+    PaymentURI result;
+    result.scheme = toLower(uri.substr(0, colon));
+    if (result.scheme != schemeForCoin(coin)) {
+        // e.g. a litecoin: URI, with an address which may be valid for this coin too
+        return std::nullopt;
+    }
+    const auto question = uri.find('?', colon + 1);
+    const auto address = percentDecode(uri.substr(colon + 1, question == std::string::npos ? std::string::npos : question - colon - 1));
+    if (!address.has_value()) {
+        return std::nullopt;
+    }
+    result.address = address.value();
+
+    if (question != std::string::npos) {
+        std::map<std::string, std::string> params;
+        auto start = question + 1;
+        while (start <= uri.size()) {
+            auto end = uri.find('&', start);
+            if (end == std::string::npos) {
+                end = uri.size();
+            }
+            const auto param = uri.substr(start, end - start);
+            start = end + 1;
+            if (param.empty()) {
+                continue;
+            }
+            const auto equals = param.find('=');
+            const auto key = toLower(param.substr(0, equals));
+            const auto value = percentDecode(equals == std::string::npos ? std::string() : param.substr(equals + 1));
+            if (!value.has_value() || params.find(key) != params.end()) {
+                return std::nullopt;
+            }
+            params[key] = value.value();
+        }
+
+        for (auto& [key, value] : params) {
+            if (key == "amount") {
+                result.amount = parseAmount(value, coin);
+                if (!result.amount.has_value()) {
+                    return std::nullopt;
+                }
+            } else if (key == "label") {
+                result.label = value;
+            } else if (key == "message") {
+                result.message = value;
+            } else if (key == "lightning") {
+                result.lightning = value;
+            } else if (key == "pj") {
+                result.payjoin = value;
+            } else if (key.rfind("req-", 0) == 0) {
+                // unknown required parameter
+                return std::nullopt;
+            } else {
+                result.otherParams[key] = value;
+            }
+        }
+    }
+
+    if (result.address.empty()) {
+        if (result.lightning.empty()) {
+            return std::nullopt;
+        }
+    } else if (Script::lockScriptForAddress(result.address, coin).empty()) {
+        return std::nullopt;
+    }
+    return result;
+}
+
+std::string PaymentURI::string() const {
+    std::string result = scheme + ":" + address;
+    std::string separator = "?";
+    const auto addParam = [&result, &separator](const std::string& key, const std::string& value) {
+        result += separator + key + "=" + value;
+        separator = "&";
+    };
+    if (amount.has_value()) {
+        addParam("amount", formatAmount(amount.value()));
+    }
+    if (!label.empty()) {
+        addParam("label", percentEncode(label));
+    }
+    if (!message.empty()) {
+        addParam("message", percentEncode(message));
+    }
+    if (!lightning.empty()) {
+        addParam("lightning", percentEncode(lightning));
+    }
+    if (!payjoin.empty()) {
+        addParam("pj", percentEncode(payjoin));
+    }
+    for (auto& [key, value] : otherParams) {
+        addParam(key, percentEncode(value));
+    }
+    return result;
+}
+
+bool PaymentURI::applyTo(SigningInput& input) const {
+    if (address.empty()) {
+        return false;
+    }
+    input.toAddress = address;
+    if (amount.has_value()) {
+        input.amount = amount.value();
+    }
+    return true;
+}
+
+} // namespace TW::Bitcoin
@@ -0,0 +1,78 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+#pragma once
+
+#include "Amount.h"
+#include "SigningInput.h"
+#include <TrustWalletCore/TWCoinType.h>
+
+#include <map>
+#include <optional>
+#include <string>
+
+namespace TW::Bitcoin {
+
+/// Payment request URI, as defined by BIP21:
+/// `bitcoin:<address>[?amount=<amount>][&label=<label>][&message=<message>]`.
+/// Common extension parameters (`lightning`, `pj` for PayJoin) are supported as well.
+struct PaymentURI {
+    /// URI scheme, e.g. "bitcoin"
+    std::string scheme = "bitcoin";
+
+    /// Destination address; may be empty only if there is a lightning invoice
+    std::string address;
+
+    /// Requested amount in satoshis, if specified
+    std::optional<Amount> amount;
+
+    /// Label for the address (e.g. name of the receiver)
+    std::string label;
+
+    /// Message describing the payment
+    std::string message;
+
+    /// BOLT11 lightning invoice, alternative payment method
+    std::string lightning;
+
+    /// PayJoin (BIP78) endpoint
+    std::string payjoin;
+
+    /// Other, unknown (non-required) parameters
+    std::map<std::string, std::string> otherParams;
+
+    /// Parses a payment URI.  Scheme is case-insensitive, parameter values are percent-decoded.
+    /// Returns nullopt if the URI is invalid: no scheme or not the scheme of the coin, invalid address or amount,
+    /// duplicate parameter, or an unknown required (`req-`) parameter.
+    static std::optional<PaymentURI> parse(const std::string& uri, TWCoinType coin = TWCoinTypeBitcoin);
+
+    /// Returns the BIP21-style URI scheme of the coin (e.g. "litecoin"), or empty string if the coin has none.
+    static std::string schemeForCoin(TWCoinType coin);
+
+    /// Returns the URI string, with percent-encoded parameter values.
+    std::string string() const;
+
+    /// Sets the destination address and the amount (if any) of the signing input.
+    /// Returns false if there is no on-chain address to pay to.
+    bool applyTo(SigningInput& input) const;
+
+    /// Parses a decimal amount in coins (e.g. "0.001") into satoshis, at most 8 decimals.
+    /// Returns nullopt if the amount is above the maximum of the coin (see maxAmount).
+    static std::optional<Amount> parseAmount(const std::string& amount, TWCoinType coin = TWCoinTypeBitcoin);
+
+    /// Returns the largest valid amount of the coin in satoshis (the total money supply limit, MAX_MONEY).
+    static Amount maxAmount(TWCoinType coin);
+
+    /// Formats an amount in satoshis as decimal amount in coins, without trailing zeroes.
+    static std::string formatAmount(Amount amount);
+};
+
+} // namespace TW::Bitcoin
+
+/// Wrapper for C interface.
+struct TWBitcoinPaymentURI {
+    TW::Bitcoin::PaymentURI impl;
+};
@@ -0,0 +1,47 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+#include "RelativeLockTime.h"
+
+#include <algorithm>
+
+namespace TW::Bitcoin {
+
+std::optional<RelativeLockTime> RelativeLockTime::fromSeconds(uint32_t seconds) {
+    const uint32_t unitSeconds = 1u << Granularity;
+    const uint64_t units = (static_cast<uint64_t>(seconds) + unitSeconds - 1) / unitSeconds;
+    if (units > ValueMask) {
+        return std::nullopt;
+    }
+    return RelativeLockTime{Time, static_cast<uint16_t>(units)};
+}
+
+std::optional<RelativeLockTime> RelativeLockTime::fromSequence(uint32_t sequence) {
+    if ((sequence & DisableFlag) != 0) {
+        return std::nullopt;
+    }
+    return RelativeLockTime{(sequence & TypeFlag) != 0 ? Time : Blocks, static_cast<uint16_t>(sequence & ValueMask)};
+}
+
+uint32_t RelativeLockTime::sequence() const {
+    return (unit == Time ? TypeFlag : 0) | value;
+}
+
+std::optional<RelativeLockTime> RelativeLockTime::combine(const std::vector<RelativeLockTime>& requirements) {
+    if (requirements.empty()) {
+        return RelativeLockTime{};
+    }
+    RelativeLockTime result{requirements.front().unit, 0};
+    for (auto& required : requirements) {
+        if (required.unit != result.unit) {
+            return std::nullopt;
+        }
+        result.value = std::max(result.value, required.value);
+    }
+    return result;
+}
+
+} // namespace TW::Bitcoin
@@ -0,0 +1,57 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+#pragma once
+
+#include <cstdint>
+#include <optional>
+#include <vector>
+
+namespace TW::Bitcoin {
+
+/// Relative lock-time of an input, as encoded in its sequence number (BIP68),
+/// and as required by OP_CHECKSEQUENCEVERIFY (BIP112).  Requires transaction version 2 or higher.
+struct RelativeLockTime {
+    enum Unit {
+        Blocks,
+        Time, // in units of 512 seconds
+    };
+
+    Unit unit = Blocks;
+    uint16_t value = 0;
+
+    /// If set in the sequence, there is no relative lock-time
+    static constexpr uint32_t DisableFlag = 1u << 31;
+    /// If set in the sequence, the value is time (in 512 s units), otherwise blocks
+    static constexpr uint32_t TypeFlag = 1u << 22;
+    static constexpr uint32_t ValueMask = 0x0000ffff;
+    /// Time values are in units of 2^9 = 512 seconds
+    static constexpr uint32_t Granularity = 9;
+
+    static RelativeLockTime fromBlocks(uint16_t blocks) { return RelativeLockTime{Blocks, blocks}; }
+
+    /// Relative lock-time from a duration in seconds, rounded up to the 512-second granularity.
+    /// Returns nullopt if the duration is too long (more than 65535 * 512 seconds, about 388 days).
+    static std::optional<RelativeLockTime> fromSeconds(uint32_t seconds);
+
+    /// Decodes the sequence number of an input.  Returns nullopt if relative lock-time is disabled.
+    static std::optional<RelativeLockTime> fromSequence(uint32_t sequence);
+
+    /// Encodes as input sequence number.
+    uint32_t sequence() const;
+
+    /// Duration in seconds, for time-based lock-times (0 for block-based).
+    uint32_t seconds() const { return unit == Time ? static_cast<uint32_t>(value) << Granularity : 0; }
+
+    /// Whether this lock-time (of an input) satisfies a CSV requirement: same unit, and at least as long.
+    bool satisfies(const RelativeLockTime& required) const { return unit == required.unit && value >= required.value; }
+
+    /// Combines several CSV requirements on the same input into the single (smallest) lock-time which satisfies all.
+    /// Returns nullopt if they cannot be satisfied together (block- and time-based requirements mixed).
+    static std::optional<RelativeLockTime> combine(const std::vector<RelativeLockTime>& requirements);
+};
+
+} // namespace TW::Bitcoin
@@ -7,6 +7,7 @@
 #include "Address.h"
 #include "CashAddress.h"
 #include "OpCodes.h"
+#include "RelativeLockTime.h"
 #include "Script.h"
 #include "SegwitAddress.h"
 #include <TrustWalletCore/TWHRP.h>
@@ -15,11 +16,14 @@
 #include "../Coin.h"
 #include "../Decred/Address.h"
 #include "../Groestlcoin/Address.h"
+#include "../HexCoding.h"
 #include "../Zcash/TAddress.h"
 
 #include <algorithm>
 #include <iterator>
 #include <cassert>
+#include <map>
+#include <optional>
 
 namespace TW::Bitcoin {
 
@@ -53,6 +57,17 @@ bool Script::isWitnessProgram() const {
     return bytes[1] + 2 == bytes.size();
 }
 
+bool Script::isFutureWitnessProgram() const {
+    if (!isWitnessProgram()) {
+        return false;
+    }
+    if (bytes[0] == OP_0) {
+        return false;
+    }
+    // v1 with 32-byte program is P2TR
+    return !(bytes[0] == OP_1 && bytes.size() == 34);
+}
+
 bool Script::matchPayToPublicKey(Data& result) const {
     if (bytes.size() == PublicKey::secp256k1ExtendedSize + 2 &&
         bytes[0] == PublicKey::secp256k1ExtendedSize && bytes.back() == OP_CHECKSIG) {
@@ -229,17 +244,23 @@ Script Script::buildPayToScriptHash(const Data& scriptHash) {
     return script;
 }
 
-// Append to the buffer the length for the upcoming data (push). Supported length range: 0-75 bytes
+// Append to the buffer the length for the upcoming data (push). Supported length range: 0-65535 bytes
 void pushDataLength(Data& buffer, size_t len) {
-    assert(len <= 255);
+    assert(len <= 0xffff);
     if (len < static_cast<byte>(OP_PUSHDATA1)) {
         // up to 75 bytes, simple OP_PUSHBYTES with len
         buffer.push_back(static_cast<byte>(len));
         return;
     }
-    // 75 < len < 256, OP_PUSHDATA with 1-byte len
-    buffer.push_back(OP_PUSHDATA1);
-    buffer.push_back(static_cast<byte>(len));
+    if (len <= 0xff) {
+        // 75 < len < 256, OP_PUSHDATA with 1-byte len
+        buffer.push_back(OP_PUSHDATA1);
+        buffer.push_back(static_cast<byte>(len));
+        return;
+    }
+    // 255 < len < 65536, OP_PUSHDATA2 with 2-byte len
+    buffer.push_back(OP_PUSHDATA2);
+    encode16LE(static_cast<uint16_t>(len), buffer);
 }
 
 Script Script::buildPayToV0WitnessProgram(const Data& program) {
@@ -272,17 +293,35 @@ Script Script::buildPayToV1WitnessProgram(const Data& publicKey) {
     return script;
 }
 
-Script Script::buildOpReturnScript(const Data& data) {
-    if (data.size() > MaxOpReturnLength) {
+Script Script::buildPayToWitnessProgram(uint8_t version, const Data& program) {
+    assert(version <= 16);
+    assert(program.size() >= 2 && program.size() <= 40);
+    Script script;
+    script.bytes.push_back(encodeNumber(version));
+    pushDataLength(script.bytes, static_cast<byte>(program.size()));
+    append(script.bytes, program);
+    return script;
+}
+
+Script Script::buildCheckTemplateVerify(const Data& templateHash) {
+    assert(templateHash.size() == 32);
+    Script script;
+    pushDataLength(script.bytes, templateHash.size());
+    append(script.bytes, templateHash);
+    script.bytes.push_back(OP_CHECKTEMPLATEVERIFY);
+    return script;
+}
+
+Script Script::buildOpReturnScript(const Data& data, size_t maxLength) {
+    if (data.size() > maxLength || data.size() > MaxOpReturnLengthLimit) {
         // data too long, cannot fit, fail (do not truncate)
         return {};
     }
-    assert(data.size() <= MaxOpReturnLength);
     Script script;
     script.bytes.push_back(OP_RETURN);
     pushDataLength(script.bytes, data.size());
     script.bytes.insert(script.bytes.end(), data.begin(), data.begin() + data.size());
-    assert(script.bytes.size() <= 83); // max script length, must always hold
+    assert(script.bytes.size() <= data.size() + 4); // max script length, must always hold
     return script;
 }
 
@@ -291,6 +330,190 @@ void Script::encode(Data& data) const {
     std::copy(std::begin(bytes), std::end(bytes), std::back_inserter(data));
 }
 
+namespace {
+
+std::string opcodeName(uint8_t opcode) {
+    static const std::map<uint8_t, std::string> names = {
+        {OP_1NEGATE, "-1"}, {OP_RESERVED, "OP_RESERVED"},
+        {OP_NOP, "OP_NOP"}, {OP_VER, "OP_VER"}, {OP_IF, "OP_IF"}, {OP_NOTIF, "OP_NOTIF"}, {OP_VERIF, "OP_VERIF"},
+        {OP_VERNOTIF, "OP_VERNOTIF"}, {OP_ELSE, "OP_ELSE"}, {OP_ENDIF, "OP_ENDIF"}, {OP_VERIFY, "OP_VERIFY"},
+        {OP_RETURN, "OP_RETURN"},
+        {OP_TOALTSTACK, "OP_TOALTSTACK"}, {OP_FROMALTSTACK, "OP_FROMALTSTACK"}, {OP_2DROP, "OP_2DROP"},
+        {OP_2DUP, "OP_2DUP"}, {OP_3DUP, "OP_3DUP"}, {OP_2OVER, "OP_2OVER"}, {OP_2ROT, "OP_2ROT"},
+        {OP_2SWAP, "OP_2SWAP"}, {OP_IFDUP, "OP_IFDUP"}, {OP_DEPTH, "OP_DEPTH"}, {OP_DROP, "OP_DROP"},
+        {OP_DUP, "OP_DUP"}, {OP_NIP, "OP_NIP"}, {OP_OVER, "OP_OVER"}, {OP_PICK, "OP_PICK"}, {OP_ROLL, "OP_ROLL"},
+        {OP_ROT, "OP_ROT"}, {OP_SWAP, "OP_SWAP"}, {OP_TUCK, "OP_TUCK"},
+        {OP_CAT, "OP_CAT"}, {OP_SUBSTR, "OP_SUBSTR"}, {OP_LEFT, "OP_LEFT"}, {OP_RIGHT, "OP_RIGHT"}, {OP_SIZE, "OP_SIZE"},
+        {OP_INVERT, "OP_INVERT"}, {OP_AND, "OP_AND"}, {OP_OR, "OP_OR"}, {OP_XOR, "OP_XOR"}, {OP_EQUAL, "OP_EQUAL"},
+        {OP_EQUALVERIFY, "OP_EQUALVERIFY"}, {OP_RESERVED1, "OP_RESERVED1"}, {OP_RESERVED2, "OP_RESERVED2"},
+        {OP_1ADD, "OP_1ADD"}, {OP_1SUB, "OP_1SUB"}, {OP_2MUL, "OP_2MUL"}, {OP_2DIV, "OP_2DIV"}, {OP_NEGATE, "OP_NEGATE"},
+        {OP_ABS, "OP_ABS"}, {OP_NOT, "OP_NOT"}, {OP_0NOTEQUAL, "OP_0NOTEQUAL"}, {OP_ADD, "OP_ADD"}, {OP_SUB, "OP_SUB"},
+        {OP_MUL, "OP_MUL"}, {OP_DIV, "OP_DIV"}, {OP_MOD, "OP_MOD"}, {OP_LSHIFT, "OP_LSHIFT"}, {OP_RSHIFT, "OP_RSHIFT"},
+        {OP_BOOLAND, "OP_BOOLAND"}, {OP_BOOLOR, "OP_BOOLOR"}, {OP_NUMEQUAL, "OP_NUMEQUAL"},
+        {OP_NUMEQUALVERIFY, "OP_NUMEQUALVERIFY"}, {OP_NUMNOTEQUAL, "OP_NUMNOTEQUAL"}, {OP_LESSTHAN, "OP_LESSTHAN"},
+        {OP_GREATERTHAN, "OP_GREATERTHAN"}, {OP_LESSTHANOREQUAL, "OP_LESSTHANOREQUAL"},
+        {OP_GREATERTHANOREQUAL, "OP_GREATERTHANOREQUAL"}, {OP_MIN, "OP_MIN"}, {OP_MAX, "OP_MAX"}, {OP_WITHIN, "OP_WITHIN"},
+        {OP_RIPEMD160, "OP_RIPEMD160"}, {OP_SHA1, "OP_SHA1"}, {OP_SHA256, "OP_SHA256"}, {OP_HASH160, "OP_HASH160"},
+        {OP_HASH256, "OP_HASH256"}, {OP_CODESEPARATOR, "OP_CODESEPARATOR"}, {OP_CHECKSIG, "OP_CHECKSIG"},
+        {OP_CHECKSIGVERIFY, "OP_CHECKSIGVERIFY"}, {OP_CHECKMULTISIG, "OP_CHECKMULTISIG"},
+        {OP_CHECKMULTISIGVERIFY, "OP_CHECKMULTISIGVERIFY"},
+        {OP_NOP1, "OP_NOP1"}, {OP_CHECKLOCKTIMEVERIFY, "OP_CHECKLOCKTIMEVERIFY"},
+        {OP_CHECKSEQUENCEVERIFY, "OP_CHECKSEQUENCEVERIFY"}, {OP_NOP4, "OP_NOP4"}, {OP_NOP5, "OP_NOP5"},
+        {OP_NOP6, "OP_NOP6"}, {OP_NOP7, "OP_NOP7"}, {OP_NOP8, "OP_NOP8"}, {OP_NOP9, "OP_NOP9"}, {OP_NOP10, "OP_NOP10"},
+        {OP_INVALIDOPCODE, "OP_INVALIDOPCODE"},
+    };
+    if (opcode == OP_0) {
+        return "0";
+    }
+    if (TWOpCodeIsSmallInteger(opcode)) {
+        return std::to_string(Script::decodeNumber(opcode));
+    }
+    const auto name = names.find(opcode);
+    if (name == names.end()) {
+        return "OP_UNKNOWN";
+    }
+    return name->second;
+}
+
+/// Decodes a number argument (minimally encoded, little endian, sign bit in the last byte), at most 5 bytes as for lock-times
+std::optional<int64_t> decodeScriptNumber(uint8_t opcode, const Data& operand) {
+    if (opcode == OP_0 || TWOpCodeIsSmallInteger(opcode)) {
+        return Script::decodeNumber(opcode);
+    }
+    if (opcode == OP_1NEGATE) {
+        return -1;
+    }
+    if (opcode > OP_PUSHDATA4 || operand.empty() || operand.size() > 5) {
+        return std::nullopt;
+    }
+    int64_t result = 0;
+    for (auto i = 0ul; i < operand.size(); ++i) {
+        result |= static_cast<int64_t>(operand[i]) << (8 * i);
+    }
+    const auto signBit = static_cast<int64_t>(0x80) << (8 * (operand.size() - 1));
+    if ((result & signBit) != 0) {
+        return -(result & ~signBit);
+    }
+    return result;
+}
+
+} // namespace
+
+std::string Script::disassemble() const {
+    std::string result;
+    size_t index = 0;
+    uint8_t opcode;
+    Data operand;
+    while (index < bytes.size()) {
+        if (!result.empty()) {
+            result += " ";
+        }
+        if (!getScriptOp(index, opcode, operand)) {
+            result += "[error]";
+            break;
+        }
+        if (opcode > OP_0 && opcode <= OP_PUSHDATA4 && operand.size() <= 4) {
+            // short pushes as numbers, as in Bitcoin Core
+            result += std::to_string(decodeScriptNumber(opcode, operand).value_or(0));
+        } else if (opcode > OP_0 && opcode <= OP_PUSHDATA4) {
+            result += hex(operand);
+        } else {
+            result += opcodeName(opcode);
+        }
+    }
+    return result;
+}
+
+bool Script::isPushOnlyMinimal() const {
+    size_t index = 0;
+    uint8_t opcode;
+    Data operand;
+    while (index < bytes.size()) {
+        if (!getScriptOp(index, opcode, operand) || opcode > OP_16) {
+            return false;
+        }
+        if (opcode > OP_PUSHDATA4) {
+            continue; // OP_1NEGATE, OP_1 .. OP_16
+        }
+        const auto size = operand.size();
+        if (size == 1 && ((operand[0] >= 1 && operand[0] <= 16) || operand[0] == 0x81)) {
+            return false; // should be OP_1 .. OP_16 or OP_1NEGATE
+        }
+        uint8_t minimalOpcode = OP_PUSHDATA4;
+        if (size < OP_PUSHDATA1) {
+            minimalOpcode = static_cast<uint8_t>(size);
+        } else if (size <= 0xff) {
+            minimalOpcode = OP_PUSHDATA1;
+        } else if (size <= 0xffff) {
+            minimalOpcode = OP_PUSHDATA2;
+        }
+        if (opcode != minimalOpcode) {
+            return false;
+        }
+    }
+    return true;
+}
+
+std::string Script::explain() const {
+    std::vector<std::string> parts;
+    Data data;
+    std::vector<Data> keys;
+    int required = 0;
+    if (matchPayToPublicKeyHash(data)) {
+        parts.emplace_back("P2PKH");
+    } else if (isPayToScriptHash()) {
+        parts.emplace_back("P2SH");
+    } else if (isPayToWitnessPublicKeyHash()) {
+        parts.emplace_back("P2WPKH");
+    } else if (isPayToWitnessScriptHash()) {
+        parts.emplace_back("P2WSH");
+    } else if (isWitnessProgram() && bytes[0] == OP_1 && bytes.size() == 34) {
+        // v1 with 32-byte program
+        parts.emplace_back("P2TR");
+    } else if (isWitnessProgram()) {
+        parts.emplace_back("witness v" + std::to_string(decodeNumber(bytes[0])) + " program");
+    } else if (matchPayToPublicKey(data)) {
+        parts.emplace_back("P2PK");
+    } else if (matchMultisig(keys, required)) {
+        parts.emplace_back(std::to_string(required) + "-of-" + std::to_string(keys.size()) + " CHECKMULTISIG");
+    } else if (!bytes.empty() && bytes[0] == OP_RETURN) {
+        size_t index = 1;
+        uint8_t opcode;
+        size_t dataSize = 0;
+        while (getScriptOp(index, opcode, data)) {
+            dataSize += data.size();
+        }
+        parts.emplace_back("OP_RETURN " + std::to_string(dataSize) + " bytes");
+    }
+
+    // lock-time conditions: a number followed by CSV or CLTV
+    size_t index = 0;
+    uint8_t opcode;
+    Data operand;
+    std::optional<int64_t> lastNumber;
+    while (getScriptOp(index, opcode, operand)) {
+        if (opcode == OP_CHECKSEQUENCEVERIFY && lastNumber.has_value() && *lastNumber >= 0) {
+            const auto lockTime = RelativeLockTime::fromSequence(static_cast<uint32_t>(*lastNumber));
+            if (lockTime.has_value() && lockTime->unit == RelativeLockTime::Blocks) {
+                parts.emplace_back("CSV " + std::to_string(lockTime->value) + " blocks");
+            } else if (lockTime.has_value()) {
+                parts.emplace_back("CSV " + std::to_string(lockTime->seconds()) + " seconds");
+            }
+        } else if (opcode == OP_CHECKLOCKTIMEVERIFY && lastNumber.has_value() && *lastNumber >= 0) {
+            // values below 500'000'000 are block heights, above are unix timestamps
+            const auto isHeight = *lastNumber < 500'000'000;
+            parts.emplace_back(std::string("CLTV ") + (isHeight ? "block " : "time ") + std::to_string(*lastNumber));
+        }
+        lastNumber = decodeScriptNumber(opcode, operand);
+    }
+
+    std::string result;
+    for (auto& part : parts) {
+        result += (result.empty() ? "" : ", ") + part;
+    }
+    return result;
+}
+
 Script Script::lockScriptForAddress(const std::string& string, enum TWCoinType coin) {
     // First try legacy address, for all coins
     if (Address::isValid(string)) {
@@ -327,7 +550,8 @@ Script Script::lockScriptForAddress(const std::string& string, enum TWCoinType c
         if (address.witnessVersion == 1 && address.witnessProgram.size() == 32) {
             return buildPayToV1WitnessProgram(address.witnessProgram);
         }
-        return {};
+        // future witness version (forward compatibility, BIP350); currently anyone-can-spend
+        return buildPayToWitnessProgram(address.witnessVersion, address.witnessProgram);
     }
 
     // Thirdly, coin-specific address formats
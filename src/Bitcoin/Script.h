@@ -20,9 +20,12 @@ namespace TW::Bitcoin {
 
 class Script {
   public:
-    // Maximum length for OP_RETURN data
+    // Maximum length for OP_RETURN data (default standardness policy)
     static const size_t MaxOpReturnLength = 80;
 
+    // Maximum length for OP_RETURN data with a custom policy (limited by OP_PUSHDATA2)
+    static const size_t MaxOpReturnLengthLimit = 0xffff;
+
     /// Script raw bytes.
     Data bytes;
 
@@ -54,6 +57,10 @@ class Script {
     /// Determines whether this is a witness program script.
     bool isWitnessProgram() const;
 
+    /// Determines whether this is a witness program of a version/length without defined semantics yet
+    /// (i.e. not P2WPKH, P2WSH or P2TR).  Such outputs are currently spendable by anyone.
+    bool isFutureWitnessProgram() const;
+
     /// Matches the script to a pay-to-public-key (P2PK) script.
     bool matchPayToPublicKey(Data& publicKey) const;
 
@@ -94,8 +101,14 @@ class Script {
     /// Builds a V1 pay-to-witness-program script, P2TR (from a 32-byte Schnorr public key).
     static Script buildPayToV1WitnessProgram(const Data& publicKey);
 
-    /// Builds an OP_RETURN script with given data. Returns empty script on error, if data is too long (>80).
-    static Script buildOpReturnScript(const Data& data);
+    /// Builds a pay-to-witness-program script with any version (0-16) and a program of 2-40 bytes (BIP141).
+    static Script buildPayToWitnessProgram(uint8_t version, const Data& program);
+
+    /// Builds a bare OP_CHECKTEMPLATEVERIFY script (BIP119) from a 32-byte template hash. Experimental.
+    static Script buildCheckTemplateVerify(const Data& templateHash);
+
+    /// Builds an OP_RETURN script with given data. Returns empty script on error, if data is too long (>80, or >maxLength).
+    static Script buildOpReturnScript(const Data& data, size_t maxLength = MaxOpReturnLength);
 
     /// Builds a appropriate lock script for the given
     /// address.
@@ -104,6 +117,19 @@ class Script {
     /// Encodes the script.
     void encode(Data& data) const;
 
+    /// Disassembles the script, in the format of Bitcoin Core's asm (e.g. "OP_DUP OP_HASH160 <hex> OP_EQUALVERIFY OP_CHECKSIG"):
+    /// pushes of up to 4 bytes and small integers as numbers, longer pushes as hex.  A malformed (truncated) push ends with "[error]".
+    std::string disassemble() const;
+
+    /// Human-readable description of the script: its standard template (e.g. "P2WPKH", "2-of-3 CHECKMULTISIG"),
+    /// and any lock-time conditions (e.g. "CSV 144 blocks", "CLTV block 800000"), separated by ", ".
+    /// Returns empty string if nothing is recognized.
+    std::string explain() const;
+
+    /// Whether the script consists of data pushes only, each in its minimal encoding (BIP62).
+    /// A scriptSig not satisfying this can be re-encoded by a third party, changing the txid.
+    bool isPushOnlyMinimal() const;
+
     /// Encodes a small integer
     static inline uint8_t encodeNumber(int n) {
         assert(n >= 0 && n <= 16);
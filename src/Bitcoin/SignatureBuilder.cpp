@@ -15,6 +15,8 @@
 #include "../Zcash/Transaction.h"
 #include "../Zcash/TransactionBuilder.h"
 
+#include <type_traits>
+
 namespace TW::Bitcoin {
 
 template <typename Transaction>
@@ -31,6 +33,10 @@ Result<Transaction, Common::Proto::SigningError> SignatureBuilder<Transaction>::
     transactionToSign.inputs.clear();
     std::copy(std::begin(_transaction.inputs), std::end(_transaction.inputs),
               std::back_inserter(transactionToSign.inputs));
+    if constexpr (std::is_base_of_v<Bitcoin::Transaction, Transaction>) {
+        // only the input scripts and witnesses change while signing
+        transactionToSign.cacheSigHashes();
+    }
 
     const auto hashSingle = hashTypeIsSingle(input.hashType);
     for (auto i = 0ul; i < plan.utxos.size(); i++) {
@@ -51,6 +57,9 @@ Result<Transaction, Common::Proto::SigningError> SignatureBuilder<Transaction>::
     if ((input.byteFee > 0) && (plan.fee > 0)) {
         transactionToSign.previousEstimatedVirtualSize = static_cast<int>(plan.fee / input.byteFee);
     }
+    if constexpr (std::is_base_of_v<Bitcoin::Transaction, Transaction>) {
+        transactionToSign.clearSigHashCache();
+    }
 
     return Result<Transaction, Common::Proto::SigningError>::success(std::move(transactionToSign));
 }
@@ -163,8 +172,8 @@ Result<std::vector<Data>, Common::Proto::SigningError> SignatureBuilder<Transact
             auto keyHash = Hash::ripemd(Hash::sha256(pubKey));
             auto pair = keyPairForPubKeyHash(keyHash);
             if (!pair.has_value() && signingMode == SigningMode_Normal) {
-                // Error: missing key
-                return Result<std::vector<Data>, Common::Proto::SigningError>::failure(Common::Proto::Error_missing_private_key);
+                // not our key: sign with the keys we have, signatures stay in public key order
+                continue;
             }
             auto signature = createSignature(transactionToSign, script, keyHash, pair, index, utxo.amount, version);
             if (signature.empty()) {
@@ -173,6 +182,10 @@ Result<std::vector<Data>, Common::Proto::SigningError> SignatureBuilder<Transact
             }
             results.push_back(signature);
         }
+        if (results.size() < required + 1ul && signingMode == SigningMode_Normal) {
+            // Error: missing keys
+            return Result<std::vector<Data>, Common::Proto::SigningError>::failure(Common::Proto::Error_missing_private_key);
+        }
         results.resize(required + 1);
         return Result<std::vector<Data>, Common::Proto::SigningError>::success(std::move(results));
     }
@@ -243,8 +256,10 @@ Data SignatureBuilder<Transaction>::createSignature(
                                                       static_cast<SignatureVersion>(version));
 
     if (signingMode == SigningMode_HashOnly) {
-        // Don't sign, only store hash-to-be-signed + pubkeyhash.  Return placeholder.
+        // Don't sign, only store hash-to-be-signed + pubkeyhash, and the pre-image.  Return placeholder.
         hashesForSigning.push_back(std::make_pair(sighash, publicKeyHash));
+        preImagesForSigning.push_back(transaction.getSignaturePreImage(script, index, input.hashType, amount,
+                                                                       static_cast<SignatureVersion>(version)));
         return Data(72);
     }
 
@@ -53,6 +53,9 @@ private:
     /// For SigningMode_HashOnly, collect hashes (plus corresponding publickey hashes) here
     HashPubkeyList hashesForSigning;
 
+    /// For SigningMode_HashOnly, the pre-images of the hashes (same order)
+    std::vector<Data> preImagesForSigning;
+
     /// For SigningMode_External, signatures are provided here
     std::optional<SignaturePubkeyList> externalSignatures;
 
@@ -79,6 +82,8 @@ public:
 
     HashPubkeyList getHashesForSigning() const { return hashesForSigning; }
 
+    std::vector<Data> getPreImagesForSigning() const { return preImagesForSigning; }
+
 private:
     Result<void, Common::Proto::SigningError> sign(Script script, size_t index, const UTXO& utxo);
     Result<std::vector<Data>, Common::Proto::SigningError> signStep(Script script, size_t index,
@@ -5,7 +5,6 @@
 // file LICENSE at the root of the source code distribution tree.
 
 #include "Signer.h"
-#include "Hash.h"
 #include "HexCoding.h"
 #include "Transaction.h"
 #include "TransactionBuilder.h"
@@ -20,6 +19,22 @@ Proto::TransactionPlan Signer::plan(const Proto::SigningInput& input) noexcept {
     return plan.proto();
 }
 
+Proto::SplitPaymentPlans Signer::planSplitPayment(const Proto::SigningInput& input) noexcept {
+    Proto::SplitPaymentPlans output;
+    const auto signingInput = SigningInput(input);
+    const auto plans = TransactionBuilder::planSplitPayment(signingInput);
+    if (plans.size() == 1 && plans.front().error != Common::Proto::OK) {
+        output.set_error(plans.front().error);
+        output.set_error_message(Common::Proto::SigningError_Name(plans.front().error));
+        return output;
+    }
+    for (auto i = 0ul; i < plans.size(); ++i) {
+        *output.add_plans() = plans[i].proto();
+        output.add_change_addresses(TransactionBuilder::splitPaymentChangeAddress(signingInput, i));
+    }
+    return output;
+}
+
 Proto::SigningOutput Signer::sign(const Proto::SigningInput& input, std::optional<SignaturePubkeyList> optionalExternalSigs) noexcept {
     Proto::SigningOutput output;
     auto result = TransactionSigner<Transaction, TransactionBuilder>::sign(input, false, optionalExternalSigs);
@@ -35,14 +50,7 @@ Proto::SigningOutput Signer::sign(const Proto::SigningInput& input, std::optiona
     tx.encode(encoded);
     output.set_encoded(encoded.data(), encoded.size());
 
-    Data txHashData = encoded;
-    if (tx.hasWitness()) {
-        txHashData.clear();
-        tx.encode(txHashData, Transaction::SegwitFormatMode::NonSegwit);
-    }
-    auto txHash = Hash::sha256d(txHashData.data(), txHashData.size());
-    std::reverse(txHash.begin(), txHash.end());
-    output.set_transaction_id(hex(txHash));
+    output.set_transaction_id(hex(tx.txid()));
     return output;
 }
 
@@ -55,12 +63,17 @@ Proto::PreSigningOutput Signer::preImageHashes(const Proto::SigningInput& input)
         return output;
     }
 
-    auto hashList = result.payload();
+    const auto& hashList = result.payload().hashes;
+    const auto& preImages = result.payload().preImages;
     auto* hashPubKeys = output.mutable_hash_public_keys();
-    for (auto& h : hashList) {
+    for (auto i = 0ul; i < hashList.size(); ++i) {
+        auto& h = hashList[i];
         auto* hpk = hashPubKeys->Add();
         hpk->set_data_hash(h.first.data(), h.first.size());
         hpk->set_public_key_hash(h.second.data(), h.second.size());
+        if (i < preImages.size()) {
+            hpk->set_preimage(preImages[i].data(), preImages[i].size());
+        }
     }
     return output;
 }
@@ -24,6 +24,9 @@ class Signer {
     /// Returns a transaction plan (utxo selection, fee estimation)
     static Proto::TransactionPlan plan(const Proto::SigningInput& input) noexcept;
 
+    /// Returns the plans of a payment split into several transactions, one for each address (merge avoidance)
+    static Proto::SplitPaymentPlans planSplitPayment(const Proto::SigningInput& input) noexcept;
+
     /// Signs a Proto::SigningInput transaction
     static Proto::SigningOutput sign(const Proto::SigningInput& input, std::optional<SignaturePubkeyList> optionalExternalSigs = {}) noexcept;
 
@@ -6,6 +6,8 @@
 
 #include "SigningInput.h"
 
+#include <algorithm>
+
 namespace TW::Bitcoin {
 
 SigningInput::SigningInput(const Proto::SigningInput& input) {
@@ -30,6 +32,31 @@ SigningInput::SigningInput(const Proto::SigningInput& input) {
     }
     outputOpReturn = data(input.output_op_return());
     lockTime = input.lock_time();
+    subtractFeeFromAmount = input.subtract_fee_from_amount();
+    changeOutputCount = std::max(1u, input.change_output_count());
+    randomizeChangeAmounts = input.randomize_change_amounts();
+    randomSeed = input.random_seed();
+    for (auto&& address : input.used_addresses()) {
+        usedAddresses.push_back(address);
+    }
+    rejectAddressReuse = input.reject_address_reuse();
+    for (auto&& address : input.split_change_addresses()) {
+        splitChangeAddresses.push_back(address);
+    }
+    minConfirmations = input.min_confirmations();
+    preferOldestUtxos = input.prefer_oldest_utxos();
+    for (auto&& output : input.extra_outputs()) {
+        extraOutputs.emplace_back(output.to_address(), output.amount());
+    }
+    for (auto&& opReturn : input.extra_op_returns()) {
+        extraOpReturns.push_back(data(opReturn));
+    }
+    if (input.max_op_return_length() > 0) {
+        maxOpReturnLength = input.max_op_return_length();
+    }
+    randomizeUtxoSelection = input.randomize_utxo_selection();
+    version = input.version();
+    blockHeight = input.block_height();
 }
 
 } // namespace TW::Bitcoin
@@ -8,6 +8,7 @@
 
 #include "Amount.h"
 #include "Transaction.h"
+#include "Script.h"
 #include "UTXO.h"
 #include <TrustWalletCore/TWBitcoinSigHashType.h>
 #include "../proto/Bitcoin.pb.h"
@@ -16,6 +17,7 @@
 #include <vector>
 #include <map>
 #include <optional>
+#include <utility>
 
 namespace TW::Bitcoin {
 
@@ -61,6 +63,51 @@ public:
 
     uint32_t lockTime = 0;
 
+    // If set, the fee is deducted from the amount sent to the recipient
+    bool subtractFeeFromAmount = false;
+
+    // Number of change outputs to split the change into
+    uint32_t changeOutputCount = 1;
+
+    // If the change outputs should have random amounts, instead of equal ones
+    bool randomizeChangeAmounts = false;
+
+    // Seed for the random selection and change amounts, 0 for random seed
+    uint64_t randomSeed = 0;
+
+    // Addresses already used by the wallet
+    std::vector<std::string> usedAddresses;
+
+    // If planning should fail on address reuse
+    bool rejectAddressReuse = false;
+
+    // Change addresses of the transactions of a split payment, one for each
+    std::vector<std::string> splitChangeAddresses;
+
+    // Minimum number of confirmations of the UTXOs to use
+    uint32_t minConfirmations = 0;
+
+    // If UTXOs should be selected oldest first
+    bool preferOldestUtxos = false;
+
+    // Additional outputs (address, amount), for batched payments
+    std::vector<std::pair<std::string, Amount>> extraOutputs;
+
+    // Additional OP_RETURN outputs
+    std::vector<Data> extraOpReturns;
+
+    // Maximum length of OP_RETURN data
+    uint32_t maxOpReturnLength = Script::MaxOpReturnLength;
+
+    // If UTXOs should be selected in random order, whole address groups at a time
+    bool randomizeUtxoSelection = false;
+
+    // Transaction version, 0 for the default
+    int32_t version = 0;
+
+    // Current block height, 0 if unknown
+    uint32_t blockHeight = 0;
+
 public:
     SigningInput() = default;
 
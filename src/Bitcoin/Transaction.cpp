@@ -5,12 +5,14 @@
 // file LICENSE at the root of the source code distribution tree.
 
 #include "Transaction.h"
+#include "RelativeLockTime.h"
 #include "SegwitAddress.h"
 #include "SignatureVersion.h"
 #include "SigHashType.h"
 
 #include "../BinaryCoding.h"
 
+#include <algorithm>
 #include <cassert>
 
 namespace TW::Bitcoin {
@@ -73,6 +75,9 @@ Data Transaction::getPreImage(const Script& scriptCode, size_t index,
 }
 
 Data Transaction::getPrevoutHash() const {
+    if (sigHashCache.has_value()) {
+        return sigHashCache->prevoutHash;
+    }
     Data data;
     for (auto& input : inputs) {
         auto& outpoint = reinterpret_cast<const OutPoint&>(input.previousOutput);
@@ -83,6 +88,9 @@ Data Transaction::getPrevoutHash() const {
 }
 
 Data Transaction::getSequenceHash() const {
+    if (sigHashCache.has_value()) {
+        return sigHashCache->sequenceHash;
+    }
     Data data;
     for (auto& input : inputs) {
         encode32LE(input.sequence, data);
@@ -92,6 +100,9 @@ Data Transaction::getSequenceHash() const {
 }
 
 Data Transaction::getOutputsHash() const {
+    if (sigHashCache.has_value()) {
+        return sigHashCache->outputsHash;
+    }
     Data data;
     for (auto& output : outputs) {
         output.encode(data);
@@ -100,6 +111,11 @@ Data Transaction::getOutputsHash() const {
     return hash;
 }
 
+void Transaction::cacheSigHashes() {
+    clearSigHashCache();
+    sigHashCache = SigHashCache{getPrevoutHash(), getSequenceHash(), getOutputsHash()};
+}
+
 void Transaction::encode(Data& data, enum SegwitFormatMode segwitFormat) const {
     bool useWitnessFormat = true;
     switch (segwitFormat) {
@@ -151,6 +167,26 @@ bool Transaction::hasWitness() const {
     return std::any_of(inputs.begin(), inputs.end(), [](auto& input) { return !input.scriptWitness.empty(); });
 }
 
+Data Transaction::txid() const {
+    Data data;
+    encode(data, NonSegwit);
+    auto hash = Hash::hash(hasher, data);
+    std::reverse(hash.begin(), hash.end());
+    return hash;
+}
+
+Data Transaction::wtxid() const {
+    Data data;
+    encode(data, IfHasWitness);
+    auto hash = Hash::hash(hasher, data);
+    std::reverse(hash.begin(), hash.end());
+    return hash;
+}
+
+bool Transaction::hasMalleableScriptSig() const {
+    return std::any_of(inputs.begin(), inputs.end(), [](auto& input) { return !input.script.isPushOnlyMinimal(); });
+}
+
 Data Transaction::getSignatureHash(const Script& scriptCode, size_t index,
                                    enum TWBitcoinSigHashType hashType, uint64_t amount,
                                    enum SignatureVersion version) const {
@@ -161,6 +197,16 @@ Data Transaction::getSignatureHash(const Script& scriptCode, size_t index,
     return getSignatureHashWitnessV0(scriptCode, index, hashType, amount);
 }
 
+Data Transaction::getSignaturePreImage(const Script& scriptCode, size_t index,
+                                       enum TWBitcoinSigHashType hashType, uint64_t amount,
+                                       enum SignatureVersion version) const {
+    if (version == BASE) {
+        return getPreImageBase(scriptCode, index, hashType);
+    }
+    // version == WITNESS_V0
+    return getPreImage(scriptCode, index, hashType, amount);
+}
+
 /// Generates the signature hash for Witness version 0 scripts.
 Data Transaction::getSignatureHashWitnessV0(const Script& scriptCode, size_t index,
                                             enum TWBitcoinSigHashType hashType,
@@ -173,6 +219,14 @@ Data Transaction::getSignatureHashWitnessV0(const Script& scriptCode, size_t ind
 /// Generates the signature hash for for scripts other than witness scripts.
 Data Transaction::getSignatureHashBase(const Script& scriptCode, size_t index,
                                        enum TWBitcoinSigHashType hashType) const {
+    auto preimage = getPreImageBase(scriptCode, index, hashType);
+    auto hash = Hash::hash(hasher, preimage);
+    return hash;
+}
+
+/// Generates the signature pre-image for for scripts other than witness scripts.
+Data Transaction::getPreImageBase(const Script& scriptCode, size_t index,
+                                  enum TWBitcoinSigHashType hashType) const {
     assert(index < inputs.size());
 
     Data data;
@@ -205,8 +259,7 @@ Data Transaction::getSignatureHashBase(const Script& scriptCode, size_t index,
     // Sighash type
     encode32LE(hashType, data);
 
-    auto hash = Hash::hash(hasher, data);
-    return hash;
+    return data;
 }
 
 void Transaction::serializeInput(size_t subindex, const Script& scriptCode, size_t index,
@@ -236,6 +289,77 @@ void Transaction::serializeInput(size_t subindex, const Script& scriptCode, size
     }
 }
 
+Data Transaction::getCheckTemplateVerifyHash(uint32_t index) const {
+    Data data;
+    encode32LE(_version, data);
+    encode32LE(lockTime, data);
+
+    // scriptSigs are committed to only if there is any non-empty one
+    if (std::any_of(inputs.begin(), inputs.end(), [](auto& input) { return !input.script.empty(); })) {
+        Data scriptSigs;
+        for (auto& input : inputs) {
+            input.script.encode(scriptSigs);
+        }
+        append(data, Hash::sha256(scriptSigs));
+    }
+
+    encode32LE(static_cast<uint32_t>(inputs.size()), data);
+    Data sequences;
+    for (auto& input : inputs) {
+        encode32LE(input.sequence, sequences);
+    }
+    append(data, Hash::sha256(sequences));
+
+    encode32LE(static_cast<uint32_t>(outputs.size()), data);
+    Data outputsData;
+    for (auto& output : outputs) {
+        output.encode(outputsData);
+    }
+    append(data, Hash::sha256(outputsData));
+
+    encode32LE(index, data);
+    return Hash::sha256(data);
+}
+
+std::vector<std::string> Transaction::describeLockTimes() const {
+    std::vector<std::string> result;
+    const auto allFinal = std::all_of(inputs.begin(), inputs.end(), [](auto& input) { return input.sequence == UINT32_MAX; });
+    if (lockTime != 0 && !allFinal) {
+        if (lockTime < LockTimeThreshold) {
+            result.push_back("not valid before block " + std::to_string(lockTime));
+        } else {
+            result.push_back("not valid before time " + std::to_string(lockTime));
+        }
+    }
+    if (_version < 2) {
+        // relative lock-times are not enforced
+        return result;
+    }
+    for (auto i = 0ul; i < inputs.size(); ++i) {
+        const auto relative = RelativeLockTime::fromSequence(inputs[i].sequence);
+        if (!relative.has_value() || relative->value == 0) {
+            continue;
+        }
+        if (relative->unit == RelativeLockTime::Blocks) {
+            result.push_back("input " + std::to_string(i) + " requires " + std::to_string(relative->value) + "-block relative age");
+        } else {
+            result.push_back("input " + std::to_string(i) + " requires " + std::to_string(relative->seconds()) + "-second relative age");
+        }
+    }
+    return result;
+}
+
+bool Transaction::isLockTimeSatisfied(uint32_t blockHeight, uint32_t medianTimePast) const {
+    if (lockTime == 0) {
+        return true;
+    }
+    if (lockTime < (lockTime < LockTimeThreshold ? blockHeight : medianTimePast)) {
+        return true;
+    }
+    // the lock-time is not enforced if all inputs are final
+    return std::all_of(inputs.begin(), inputs.end(), [](auto& input) { return input.sequence == UINT32_MAX; });
+}
+
 Proto::Transaction Transaction::proto() const {
     auto protoTx = Proto::Transaction();
     protoTx.set_version(_version);
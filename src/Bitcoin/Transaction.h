@@ -18,6 +18,8 @@
 #include "SignatureVersion.h"
 #include "../proto/Bitcoin.pb.h"
 
+#include <optional>
+#include <string>
 #include <vector>
 
 namespace TW::Bitcoin {
@@ -73,6 +75,14 @@ public:
     Data getSequenceHash() const;
     Data getOutputsHash() const;
 
+    /// Computes the hashes shared by the pre-images of all inputs (BIP143 hashPrevouts, hashSequence, hashOutputs) once,
+    /// instead of for each input, which is quadratic in the number of inputs.  Changing the input outpoints or sequences,
+    /// or the outputs, after this requires clearSigHashCache(); scripts and witnesses can be changed.
+    void cacheSigHashes();
+
+    /// Drops the hashes computed by cacheSigHashes().
+    void clearSigHashCache() { sigHashCache.reset(); }
+
     enum SegwitFormatMode {
         NonSegwit,
         IfHasWitness,
@@ -90,16 +100,59 @@ public:
 
     bool hasWitness() const;
 
+    /// Transaction id: hash of the serialization without witness data, in the usual (reversed) display byte order.
+    /// It does not depend on the witnesses, so changing those does not change the txid.
+    Data txid() const;
+
+    /// Witness transaction id (BIP141): hash of the full serialization, in display byte order.  Same as txid() if there is no witness.
+    Data wtxid() const;
+
+    /// Whether the scriptSig of any input is not minimal push-only, allowing a third party to change the txid.
+    bool hasMalleableScriptSig() const;
+
+    /// Whether the other transaction is the same as this one, apart from the witness data (same txid).
+    /// Useful to match a confirmed transaction to a broadcast one, even if its witness has been changed.
+    bool isSameIgnoringWitness(const Transaction& other) const { return txid() == other.txid(); }
+
     /// Generates the signature hash for this transaction.
     Data getSignatureHash(const Script& scriptCode, size_t index, enum TWBitcoinSigHashType hashType,
                           uint64_t amount, enum SignatureVersion version) const;
 
+    /// Generates the serialized data which is hashed to obtain the signature hash (for external hashing).
+    Data getSignaturePreImage(const Script& scriptCode, size_t index, enum TWBitcoinSigHashType hashType,
+                              uint64_t amount, enum SignatureVersion version) const;
+
     void serializeInput(size_t subindex, const Script&, size_t index, enum TWBitcoinSigHashType hashType, Data& data) const;
 
+    /// Computes the BIP119 (OP_CHECKTEMPLATEVERIFY) default template hash of this transaction, for spending input `index`.
+    /// Experimental: CTV is not active on mainnet, only on signet/custom networks.
+    Data getCheckTemplateVerifyHash(uint32_t index) const;
+
+    /// Lock-time values below this are block heights, above are UNIX timestamps
+    static const uint32_t LockTimeThreshold = 500'000'000;
+
+    /// Human-readable description of the time-locks: the absolute lock-time (if enforced),
+    /// and the relative lock-times of the inputs (BIP68, version 2 or higher), e.g. "not valid before block 850000",
+    /// "input 1 requires 144-block relative age".
+    std::vector<std::string> describeLockTimes() const;
+
+    /// Whether the absolute lock-time allows the transaction in a block at the given height, with the given
+    /// median time past of the previous blocks (BIP113).  For the next block, pass the current tip height + 1.
+    /// Relative lock-times are not checked, as those depend on the age of the spent outputs.
+    bool isLockTimeSatisfied(uint32_t blockHeight, uint32_t medianTimePast) const;
+
     /// Converts to Protobuf model
     Proto::Transaction proto() const;
 
 private:
+    struct SigHashCache {
+        Data prevoutHash;
+        Data sequenceHash;
+        Data outputsHash;
+    };
+
+    std::optional<SigHashCache> sigHashCache;
+
     /// Generates the signature hash for Witness version 0 scripts.
     Data getSignatureHashWitnessV0(const Script& scriptCode, size_t index,
                                    enum TWBitcoinSigHashType hashType, uint64_t amount) const;
@@ -107,6 +160,10 @@ private:
     /// Generates the signature hash for for scripts other than witness scripts.
     Data getSignatureHashBase(const Script& scriptCode, size_t index,
                               enum TWBitcoinSigHashType hashType) const;
+
+    /// Generates the signature pre-image for for scripts other than witness scripts.
+    Data getPreImageBase(const Script& scriptCode, size_t index,
+                         enum TWBitcoinSigHashType hashType) const;
 };
 
 } // namespace TW::Bitcoin
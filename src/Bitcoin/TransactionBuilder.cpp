@@ -13,6 +13,9 @@
 
 #include <algorithm>
 #include <cassert>
+#include <iterator>
+#include <map>
+#include <random>
 
 namespace TW::Bitcoin {
 
@@ -21,6 +24,23 @@ namespace TW::Bitcoin {
 static const auto SimpleModeLimit = 1000;
 // The maximum number of UTXOs to consider.  UTXOs above this limit are cut off because it cak take very long
 const size_t TransactionBuilder::MaxUtxosHardLimit = 3000;
+const uint32_t TransactionBuilder::MaxAncestorCount = 25;
+const uint64_t TransactionBuilder::MaxAncestorSize = 101'000;
+const uint32_t TransactionBuilder::MaxLockTimeAheadBlocks = 1008;
+const int64_t TransactionBuilder::DustRelayFee = 3;
+
+Amount TransactionBuilder::dustThreshold(const Script& lockingScript) {
+    if (!lockingScript.empty() && lockingScript.bytes[0] == OP_RETURN) {
+        // unspendable
+        return 0;
+    }
+    // output: value, script length, script
+    const auto scriptSize = static_cast<int64_t>(lockingScript.bytes.size());
+    int64_t size = 8 + (scriptSize < 0xfd ? 1 : 3) + scriptSize;
+    // input: outpoint, script length, sequence, and a 107-byte signature and public key (discounted in the witness)
+    size += lockingScript.isWitnessProgram() ? 32 + 4 + 1 + 107 / 4 + 4 : 32 + 4 + 1 + 107 + 4;
+    return size * DustRelayFee;
+}
 
 std::optional<TransactionOutput> TransactionBuilder::prepareOutputWithScript(std::string address, Amount amount, enum TWCoinType coin) {
     auto lockingScript = Script::lockScriptForAddress(address, coin);
@@ -31,9 +51,26 @@ std::optional<TransactionOutput> TransactionBuilder::prepareOutputWithScript(std
 }
 
 
-/// Estimate encoded size by simple formula
+/// Estimate encoded size by simple formula.
+/// OP_RETURN outputs are counted with their actual size, which can be much larger than that of a regular output.
 int64_t estimateSimpleFee(const FeeCalculator& feeCalculator, const TransactionPlan& plan, int outputSize, int64_t byteFee) {
-    return feeCalculator.calculate(plan.utxos.size(), outputSize, byteFee);
+    const auto inputCount = static_cast<int64_t>(plan.utxos.size());
+    auto fee = feeCalculator.calculate(inputCount, outputSize, byteFee);
+    const auto outputFee = feeCalculator.calculate(inputCount, outputSize + 1, byteFee) - fee;
+    if (outputFee <= 0) {
+        // fee does not depend on the outputs
+        return fee;
+    }
+    std::vector<Data> opReturns = plan.extraOpReturns;
+    if (!plan.outputOpReturn.empty()) {
+        opReturns.push_back(plan.outputOpReturn);
+    }
+    for (auto& opReturn : opReturns) {
+        const auto scriptSize = static_cast<int64_t>(Script::buildOpReturnScript(opReturn, plan.maxOpReturnLength).bytes.size());
+        const auto opReturnSize = 8 + (scriptSize < 0xfd ? 1 : 3) + scriptSize;
+        fee += std::max(int64_t(0), opReturnSize * byteFee - outputFee);
+    }
+    return fee;
 }
 
 /// Estimate encoded size by invoking sign(sizeOnly), get actual size
@@ -77,33 +114,269 @@ int64_t estimateSegwitFee(const FeeCalculator& feeCalculator, const TransactionP
     return fee;
 }
 
+/// Random number generator with the given seed, or a random seed if 0.
+/// The output of std::mt19937_64 is fully specified, so it is the same with every standard library; the standard
+/// distributions and std::shuffle are not, so random values are drawn with explicit modulo instead.
+std::mt19937_64 seededRng(uint64_t seed) {
+    return std::mt19937_64(seed != 0 ? seed : std::random_device{}());
+}
+
+/// Split the change into `count` random amounts, each at least `minimum`.
+std::vector<Amount> randomChangeAmounts(Amount change, uint32_t count, Amount minimum, uint64_t seed) {
+    assert(count > 0 && change >= minimum * count);
+    auto rng = seededRng(seed);
+    // random cut points of the amount above the minimums
+    const auto surplus = static_cast<uint64_t>(change - minimum * count);
+    std::vector<uint64_t> cuts = {0, surplus};
+    for (auto i = 1u; i < count; ++i) {
+        cuts.push_back(rng() % (surplus + 1));
+    }
+    std::sort(cuts.begin(), cuts.end());
+    std::vector<Amount> amounts;
+    for (auto i = 0u; i < count; ++i) {
+        amounts.push_back(minimum + static_cast<Amount>(cuts[i + 1] - cuts[i]));
+    }
+    return amounts;
+}
+
+/// Collect the destination/change addresses which are in the used address list.
+/// Addresses are compared by their locking script, so different encodings of the same address match.
+std::vector<std::string> findReusedAddresses(const SigningInput& input) {
+    std::vector<std::string> reused;
+    if (input.usedAddresses.empty()) {
+        return reused;
+    }
+    std::vector<Script> usedScripts;
+    for (auto& address : input.usedAddresses) {
+        auto script = Script::lockScriptForAddress(address, input.coinType);
+        if (!script.empty()) {
+            usedScripts.push_back(script);
+        }
+    }
+    std::vector<std::string> addresses = {input.toAddress, input.changeAddress};
+    for (auto& extra : input.extraOutputs) {
+        addresses.push_back(extra.first);
+    }
+    for (auto& address : addresses) {
+        auto script = Script::lockScriptForAddress(address, input.coinType);
+        if (!script.empty() && std::find(usedScripts.begin(), usedScripts.end(), script) != usedScripts.end()) {
+            reused.push_back(address);
+        }
+    }
+    return reused;
+}
+
+/// Collect the destination addresses which pay to a witness version not defined yet (anyone-can-spend).
+std::vector<std::string> findFutureWitnessAddresses(const SigningInput& input) {
+    std::vector<std::string> addresses = {input.toAddress};
+    for (auto& extra : input.extraOutputs) {
+        addresses.push_back(extra.first);
+    }
+    std::vector<std::string> result;
+    for (auto& address : addresses) {
+        if (Script::lockScriptForAddress(address, input.coinType).isFutureWitnessProgram()) {
+            result.push_back(address);
+        }
+    }
+    return result;
+}
+
+/// Group UTXOs by locking script (address), in order of first occurrence.
+std::vector<UTXOs> groupByScript(const UTXOs& utxos) {
+    std::vector<UTXOs> groups;
+    for (auto& utxo : utxos) {
+        auto group = std::find_if(groups.begin(), groups.end(), [&utxo](const UTXOs& g) { return g.front().script == utxo.script; });
+        if (group == groups.end()) {
+            groups.emplace_back(utxo);
+        } else {
+            group->push_back(utxo);
+        }
+    }
+    return groups;
+}
+
+/// Shuffle UTXOs, keeping the UTXOs of the same address together.
+UTXOs shuffleByAddress(const UTXOs& utxos, uint64_t seed) {
+    auto groups = groupByScript(utxos);
+    // Fisher-Yates
+    auto rng = seededRng(seed);
+    for (auto i = groups.size(); i > 1; --i) {
+        std::swap(groups[i - 1], groups[static_cast<size_t>(rng() % i)]);
+    }
+    UTXOs shuffled;
+    for (auto& group : groups) {
+        shuffled.insert(shuffled.end(), group.begin(), group.end());
+    }
+    return shuffled;
+}
+
+/// Select whole address groups, in the given order, until the target value and the fee are covered, so that no coins
+/// are left behind at an address revealed by spending from it.  Dust UTXOs are skipped.
+/// Returns empty list if there are insufficient funds.
+UTXOs selectAddressGroups(const UTXOs& utxos, int64_t targetValue, int64_t byteFee, int64_t numOutputs, const FeeCalculator& feeCalculator) {
+    const auto dustThreshold = feeCalculator.calculateSingleInput(byteFee);
+    UTXOs selected;
+    for (auto& group : groupByScript(utxos)) {
+        std::copy_if(group.begin(), group.end(), std::back_inserter(selected), [dustThreshold](const UTXO& utxo) { return utxo.amount > dustThreshold; });
+        const auto fee = feeCalculator.calculate(static_cast<int64_t>(selected.size()), numOutputs, byteFee);
+        if (!selected.empty() && static_cast<int64_t>(InputSelector<UTXO>::sum(selected)) >= targetValue + fee) {
+            return selected;
+        }
+    }
+    return {};
+}
+
+/// Whether the new transaction, together with the unconfirmed ancestors of the selected UTXOs, would exceed the
+/// mempool chain limits.  UTXOs from the same parent transaction share their ancestors, which are counted once;
+/// different parents are assumed not to share any (worst case).
+bool exceedsAncestorLimits(const TransactionPlan& plan, const FeeCalculator& feeCalculator) {
+    std::map<Data, std::pair<uint32_t, uint64_t>> parents;
+    for (auto& utxo : plan.utxos) {
+        if (utxo.ancestorCount == 0 && utxo.ancestorSize == 0) {
+            continue;
+        }
+        auto& parent = parents[Data(utxo.outPoint.hash.begin(), utxo.outPoint.hash.end())];
+        parent.first = std::max(parent.first, utxo.ancestorCount);
+        parent.second = std::max(parent.second, utxo.ancestorSize);
+    }
+    const auto outputCount = 1 + plan.extraOutputs.size() + plan.changeOutputCount + plan.extraOpReturns.size() + (plan.outputOpReturn.empty() ? 0 : 1);
+    uint64_t count = 1;
+    auto size = static_cast<uint64_t>(feeCalculator.calculate(static_cast<int64_t>(plan.utxos.size()), static_cast<int64_t>(outputCount), 1));
+    for (auto& parent : parents) {
+        count += parent.second.first;
+        size += parent.second.second;
+    }
+    return count > TransactionBuilder::MaxAncestorCount || size > TransactionBuilder::MaxAncestorSize;
+}
+
+/// Return the UTXOs eligible for selection: having enough confirmations and within mempool chain limits,
+/// ordered oldest first if preferred, or in random order if randomized.
+UTXOs eligibleUtxos(const SigningInput& input) {
+    UTXOs utxos;
+    std::copy_if(input.utxos.begin(), input.utxos.end(), std::back_inserter(utxos),
+                 [&input](const UTXO& utxo) {
+                     return !utxo.isImmatureCoinbase() &&
+                            !utxo.isRelativeLocked(input.version) &&
+                            utxo.confirmations >= input.minConfirmations &&
+                            utxo.ancestorCount < TransactionBuilder::MaxAncestorCount &&
+                            utxo.ancestorSize < TransactionBuilder::MaxAncestorSize;
+                 });
+    if (input.preferOldestUtxos) {
+        std::stable_sort(utxos.begin(), utxos.end(), [](const UTXO& lhs, const UTXO& rhs) {
+            return lhs.confirmations > rhs.confirmations;
+        });
+    } else if (input.randomizeUtxoSelection) {
+        utxos = shuffleByAddress(utxos, input.randomSeed);
+    }
+    return utxos;
+}
+
+/// Check the additional outputs: valid address and non-dust amount
+Common::Proto::SigningError checkExtraOutputs(const SigningInput& input) {
+    for (auto& extra : input.extraOutputs) {
+        const auto lockingScript = Script::lockScriptForAddress(extra.first, input.coinType);
+        if (lockingScript.empty()) {
+            return Common::Proto::Error_invalid_address;
+        }
+        if (extra.second <= 0) {
+            return Common::Proto::Error_zero_amount_requested;
+        }
+        if (extra.second < TransactionBuilder::dustThreshold(lockingScript)) {
+            return Common::Proto::Error_dust_amount_requested;
+        }
+    }
+    return Common::Proto::OK;
+}
+
+Amount extraOutputsAmount(const SigningInput& input) {
+    Amount sum = 0;
+    for (auto& extra : input.extraOutputs) {
+        sum += extra.second;
+    }
+    return sum;
+}
+
 int extraOutputCount(const SigningInput& input) {
     int count = int(input.outputOpReturn.size() > 0);
+    count += static_cast<int>(input.extraOpReturns.size());
+    count += static_cast<int>(input.extraOutputs.size());
     return count;
 }
 
+bool TransactionBuilder::isValidChangeSplit(const TransactionPlan& plan) {
+    if (plan.changeAmounts.empty()) {
+        return true;
+    }
+    Amount sum = 0;
+    for (auto amount : plan.changeAmounts) {
+        if (amount <= 0) {
+            return false;
+        }
+        sum += amount;
+    }
+    return sum == plan.change;
+}
+
+bool TransactionBuilder::isValidVersion(int32_t version, enum TWCoinType coin) {
+    if (version == 0) {
+        return true;
+    }
+    switch (coin) {
+    case TWCoinTypeZcash:
+    case TWCoinTypeZelcash:
+        // overwinter versioning, set by the Zcash transaction builder
+        return false;
+    case TWCoinTypeBitcoin:
+    case TWCoinTypeDash:
+        // v3: BIP431 (TRUC) for Bitcoin, DIP2 for Dash (only the classic transaction type, upper 16 bits 0)
+        return version >= 1 && version <= 3;
+    default:
+        return version >= 1 && version <= 2;
+    }
+}
+
 TransactionPlan TransactionBuilder::plan(const SigningInput& input) {
     TransactionPlan plan;
     if (input.outputOpReturn.size() > 0) {
         plan.outputOpReturn = input.outputOpReturn;
     }
+    plan.extraOutputs = input.extraOutputs;
+    plan.extraOpReturns = input.extraOpReturns;
+    plan.maxOpReturnLength = input.maxOpReturnLength;
+    plan.version = input.version;
+
+    plan.reusedAddresses = findReusedAddresses(input);
+    plan.futureWitnessAddresses = findFutureWitnessAddresses(input);
+
+    const auto& feeCalculator = getFeeCalculator(static_cast<TWCoinType>(input.coinType));
+    const auto extraOutputsError = checkExtraOutputs(input);
+    const auto extraAmount = extraOutputsAmount(input);
 
     bool maxAmount = input.useMaxAmount;
     if (input.amount == 0 && !maxAmount) {
         plan.error = Common::Proto::Error_zero_amount_requested;
+    } else if (!isValidVersion(input.version, static_cast<TWCoinType>(input.coinType))) {
+        plan.error = Common::Proto::Error_invalid_params;
+    } else if (input.blockHeight > 0 && input.lockTime < Transaction::LockTimeThreshold &&
+               input.lockTime > input.blockHeight + MaxLockTimeAheadBlocks) {
+        plan.error = Common::Proto::Error_lock_time_not_reached;
+    } else if (!plan.reusedAddresses.empty() && input.rejectAddressReuse) {
+        plan.error = Common::Proto::Error_address_reused;
+    } else if (extraOutputsError != Common::Proto::OK) {
+        plan.error = extraOutputsError;
     } else if (input.utxos.empty()) {
         plan.error = Common::Proto::Error_missing_input_utxos;
     } else {
-        const auto& feeCalculator = getFeeCalculator(static_cast<TWCoinType>(input.coinType));
-        auto inputSelector = InputSelector<UTXO>(input.utxos, feeCalculator);
-        auto inputSum = InputSelector<UTXO>::sum(input.utxos);
+        const auto utxos = eligibleUtxos(input);
+        auto inputSelector = InputSelector<UTXO>(utxos, feeCalculator);
+        auto inputSum = InputSelector<UTXO>::sum(utxos);
 
         // select UTXOs
         plan.amount = input.amount;
 
         // if amount requested is the same or more than available amount, it cannot be satisfied, but
         // treat this case as MaxAmount, and send maximum available (which will be less)
-        if (!maxAmount && static_cast<uint64_t>(input.amount) >= inputSum) {
+        if (!maxAmount && static_cast<uint64_t>(input.amount + extraAmount) >= inputSum) {
             maxAmount = true;
         }
 
@@ -111,11 +384,23 @@ TransactionPlan TransactionBuilder::plan(const SigningInput& input) {
         auto output_size = 2;
         UTXOs selectedInputs;
         if (!maxAmount) {
-            output_size = 2 + extraOutputs; // output + change
-            if (input.utxos.size() <= SimpleModeLimit && input.utxos.size() <= MaxUtxosHardLimit) {
-                selectedInputs = inputSelector.select(plan.amount, input.byteFee, output_size);
+            plan.changeOutputCount = std::max(uint32_t(1), input.changeOutputCount);
+            output_size = 1 + static_cast<int>(plan.changeOutputCount) + extraOutputs; // output + change(s)
+            if (input.preferOldestUtxos) {
+                selectedInputs = inputSelector.selectInOrder(plan.amount + extraAmount, input.byteFee, output_size);
+            } else if (input.randomizeUtxoSelection) {
+                selectedInputs = selectAddressGroups(utxos, plan.amount + extraAmount, input.byteFee, output_size, feeCalculator);
+            } else if (utxos.size() <= SimpleModeLimit && utxos.size() <= MaxUtxosHardLimit) {
+                selectedInputs = inputSelector.select(plan.amount + extraAmount, input.byteFee, output_size);
             } else {
-                selectedInputs = inputSelector.selectSimple(plan.amount, input.byteFee, output_size);
+                selectedInputs = inputSelector.selectSimple(plan.amount + extraAmount, input.byteFee, output_size);
+            }
+            if (selectedInputs.empty() && input.subtractFeeFromAmount) {
+                // fee is taken from the amount, it is enough to cover the amount itself
+                auto nonDustInputs = inputSelector.selectMaxAmount(input.byteFee);
+                if (InputSelector<UTXO>::sum(nonDustInputs) >= static_cast<uint64_t>(input.amount + extraAmount)) {
+                    selectedInputs = nonDustInputs;
+                }
             }
         } else {
             output_size = 1 + extraOutputs; // output, no change
@@ -131,7 +416,9 @@ TransactionPlan TransactionBuilder::plan(const SigningInput& input) {
             }
         }
 
-        if (plan.utxos.size() == 0) {
+        if (plan.utxos.size() == 0 || InputSelector<UTXO>::sum(plan.utxos) <= static_cast<uint64_t>(extraAmount)) {
+            // nothing left for the main output after the additional outputs
+            plan.utxos.clear();
             plan.amount = 0;
             plan.error = Common::Proto::Error_not_enough_utxos;
         } else {
@@ -140,40 +427,197 @@ TransactionPlan TransactionBuilder::plan(const SigningInput& input) {
             // Compute fee.
             // must preliminary set change so that there is a second output
             if (!maxAmount) {
-                assert(input.amount <= plan.availableAmount);
+                assert(input.amount + extraAmount <= plan.availableAmount);
                 plan.amount = input.amount;
                 plan.fee = 0;
-                plan.change = plan.availableAmount - plan.amount;
+                plan.change = plan.availableAmount - plan.amount - extraAmount;
             } else {
-                plan.amount = plan.availableAmount;
+                plan.amount = plan.availableAmount - extraAmount;
                 plan.fee = 0;
                 plan.change = 0;
             }
             plan.fee = estimateSegwitFee(feeCalculator, plan, output_size, input);
             // If fee is larger than availableAmount (can happen in special maxAmount case), we reduce it (and hope it will go through)
-            plan.fee = std::min(plan.availableAmount, plan.fee);
-            assert(plan.fee >= 0 && plan.fee <= plan.availableAmount);
+            plan.fee = std::min(plan.availableAmount - extraAmount, plan.fee);
+            assert(plan.fee >= 0 && plan.fee <= plan.availableAmount - extraAmount);
 
             // adjust/compute amount
-            if (!maxAmount) {
+            if (!maxAmount && input.subtractFeeFromAmount) {
+                // fee is paid by the recipient
+                plan.amount = std::max(Amount(0), plan.amount - plan.fee);
+            } else if (!maxAmount) {
                 // reduce amount if needed
-                plan.amount = std::max(Amount(0), std::min(plan.amount, plan.availableAmount - plan.fee));
+                plan.amount = std::max(Amount(0), std::min(plan.amount, plan.availableAmount - extraAmount - plan.fee));
             } else {
                 // max available amount
-                plan.amount = std::max(Amount(0), plan.availableAmount - plan.fee);
+                plan.amount = std::max(Amount(0), plan.availableAmount - extraAmount - plan.fee);
             }
             assert(plan.amount >= 0 && plan.amount <= plan.availableAmount);
 
             // compute change
-            plan.change = plan.availableAmount - plan.amount - plan.fee;
+            plan.change = plan.availableAmount - plan.amount - extraAmount - plan.fee;
+
+            const auto changeDustThreshold = dustThreshold(Script::lockScriptForAddress(input.changeAddress, input.coinType));
+            if (input.subtractFeeFromAmount && plan.change > 0 && plan.change < changeDustThreshold) {
+                // change would be dust; drop it, and the recipient gets it and the fee saved by the missing output
+                output_size -= static_cast<int>(plan.changeOutputCount);
+                plan.change = 0;
+                const auto reducedFee = std::min(plan.fee, estimateSegwitFee(feeCalculator, plan, output_size, input));
+                plan.amount = plan.availableAmount - extraAmount - reducedFee;
+                plan.fee = reducedFee;
+            }
+
+            // do not split change into dust parts; fewer outputs need less fee, return the difference
+            if (plan.changeOutputCount > 1 && plan.change > 0) {
+                const auto fittingCount = std::max(Amount(1), plan.change / std::max(Amount(1), changeDustThreshold));
+                if (fittingCount < static_cast<Amount>(plan.changeOutputCount)) {
+                    output_size -= static_cast<int>(plan.changeOutputCount - static_cast<uint32_t>(fittingCount));
+                    plan.changeOutputCount = static_cast<uint32_t>(fittingCount);
+                    const auto reducedFee = std::min(plan.fee, estimateSegwitFee(feeCalculator, plan, output_size, input));
+                    if (input.subtractFeeFromAmount) {
+                        plan.amount += plan.fee - reducedFee;
+                    } else {
+                        plan.change += plan.fee - reducedFee;
+                    }
+                    plan.fee = reducedFee;
+                }
+            }
+
+            if (input.randomizeChangeAmounts && plan.changeOutputCount > 1 && plan.change >= changeDustThreshold * plan.changeOutputCount) {
+                plan.changeAmounts = randomChangeAmounts(plan.change, plan.changeOutputCount, changeDustThreshold, input.randomSeed);
+            }
+
+            if (input.subtractFeeFromAmount && plan.amount < dustThreshold(Script::lockScriptForAddress(input.toAddress, input.coinType))) {
+                // amount left after subtracting the fee would be dust
+                plan.utxos.clear();
+                plan.availableAmount = 0;
+                plan.amount = 0;
+                plan.fee = 0;
+                plan.change = 0;
+                plan.changeAmounts.clear();
+                plan.error = Common::Proto::Error_dust_amount_requested;
+            }
         }
     }
+    if (plan.error == Common::Proto::OK && exceedsAncestorLimits(plan, feeCalculator)) {
+        // the unconfirmed chains of the selected UTXOs together are too long; plan again with confirmed UTXOs only
+        auto confirmedInput = input;
+        confirmedInput.utxos.erase(std::remove_if(confirmedInput.utxos.begin(), confirmedInput.utxos.end(),
+                                                  [](const UTXO& utxo) { return utxo.ancestorCount > 0 || utxo.ancestorSize > 0; }),
+                                   confirmedInput.utxos.end());
+        if (!input.useMaxAmount && InputSelector<UTXO>::sum(confirmedInput.utxos) <= static_cast<uint64_t>(input.amount + extraAmount)) {
+            // not enough for the requested amount; do not fall back to sending less
+            confirmedInput.utxos.clear();
+        }
+        auto confirmedPlan = TransactionBuilder::plan(confirmedInput);
+        if (confirmedPlan.error == Common::Proto::Error_not_enough_utxos || confirmedPlan.error == Common::Proto::Error_missing_input_utxos) {
+            confirmedPlan.error = Common::Proto::Error_mempool_chain_limit;
+        }
+        return confirmedPlan;
+    }
+    if (plan.error == Common::Proto::Error_not_enough_utxos &&
+        std::any_of(input.utxos.begin(), input.utxos.end(), [](const UTXO& utxo) { return utxo.isImmatureCoinbase(); })) {
+        // report the more specific reason: some of the coins cannot be spent yet
+        plan.error = Common::Proto::Error_immature_coinbase;
+    } else if (plan.error == Common::Proto::Error_not_enough_utxos &&
+               std::any_of(input.utxos.begin(), input.utxos.end(), [&input](const UTXO& utxo) { return utxo.isRelativeLocked(input.version); })) {
+        plan.error = Common::Proto::Error_lock_time_not_reached;
+    }
     assert(plan.change >= 0 && plan.change <= plan.availableAmount);
     assert(!maxAmount || plan.change == 0); // change is 0 in max amount case
 
-    assert(plan.amount + plan.change + plan.fee == plan.availableAmount);
+    assert(plan.utxos.empty() || plan.amount + extraAmount + plan.change + plan.fee == plan.availableAmount);
 
     return plan;
 }
 
+std::string TransactionBuilder::splitPaymentChangeAddress(const SigningInput& input, std::size_t index) {
+    if (input.splitChangeAddresses.empty()) {
+        // without dedicated addresses the change address can be used for a single transaction only
+        return index == 0 ? input.changeAddress : "";
+    }
+    return index < input.splitChangeAddresses.size() ? input.splitChangeAddresses[index] : "";
+}
+
+std::vector<TransactionPlan> TransactionBuilder::planSplitPayment(const SigningInput& input) {
+    const auto errorPlan = [](Common::Proto::SigningError error) {
+        TransactionPlan plan;
+        plan.error = error;
+        return std::vector<TransactionPlan>{plan};
+    };
+    if (input.subtractFeeFromAmount) {
+        // the fee of each part would have to be covered by the next one
+        return errorPlan(Common::Proto::Error_invalid_params);
+    }
+    if (input.amount == 0 || input.utxos.empty() || !input.extraOutputs.empty() ||
+        !input.outputOpReturn.empty() || !input.extraOpReturns.empty()) {
+        // let the regular planning report the error; batched payments are not split,
+        // and neither are payments with OP_RETURN data, which would be repeated in every part
+        auto singleInput = input;
+        singleInput.changeAddress = splitPaymentChangeAddress(input, 0);
+        if (singleInput.changeAddress.empty()) {
+            return errorPlan(Common::Proto::Error_invalid_params);
+        }
+        return {plan(singleInput)};
+    }
+
+    if (InputSelector<UTXO>::sum(input.utxos) < static_cast<uint64_t>(input.amount)) {
+        return errorPlan(Common::Proto::Error_not_enough_utxos);
+    }
+    const auto toDustThreshold = dustThreshold(Script::lockScriptForAddress(input.toAddress, input.coinType));
+    if (input.amount < toDustThreshold) {
+        return errorPlan(Common::Proto::Error_dust_amount_requested);
+    }
+
+    // group UTXOs by locking script
+    auto groups = groupByScript(input.utxos);
+    // use larger groups first, to minimize the number of transactions
+    std::stable_sort(groups.begin(), groups.end(), [](const UTXOs& lhs, const UTXOs& rhs) {
+        return InputSelector<UTXO>::sum(lhs) > InputSelector<UTXO>::sum(rhs);
+    });
+
+    std::vector<TransactionPlan> plans;
+    Amount remaining = input.amount;
+    for (auto& group : groups) {
+        if (remaining <= 0) {
+            break;
+        }
+        auto groupInput = input;
+        groupInput.utxos = group;
+        groupInput.amount = remaining;
+        groupInput.useMaxAmount = false;
+        // a separate change address for each transaction, not to link them together
+        groupInput.changeAddress = splitPaymentChangeAddress(input, plans.size());
+        if (groupInput.changeAddress.empty()) {
+            return errorPlan(Common::Proto::Error_invalid_params);
+        }
+        auto groupPlan = plan(groupInput);
+        if (groupPlan.error == Common::Proto::Error_not_enough_utxos) {
+            // remaining amount plus fee does not fit, spend the whole group
+            groupInput.useMaxAmount = true;
+            groupPlan = plan(groupInput);
+        }
+        if (const auto left = remaining - groupPlan.amount; groupPlan.error == Common::Proto::OK && left > 0 && left < toDustThreshold && remaining > toDustThreshold) {
+            // the part left for the next transaction would be dust; leave enough for a non-dust one
+            groupInput.useMaxAmount = false;
+            groupInput.amount = remaining - toDustThreshold;
+            groupPlan = plan(groupInput);
+        }
+        if (groupPlan.error == Common::Proto::Error_not_enough_utxos || (groupPlan.error == Common::Proto::OK && groupPlan.amount < toDustThreshold)) {
+            // this group cannot contribute a non-dust part (e.g. dust only)
+            continue;
+        }
+        if (groupPlan.error != Common::Proto::OK) {
+            return {groupPlan};
+        }
+        remaining -= groupPlan.amount;
+        plans.push_back(groupPlan);
+    }
+
+    if (remaining > 0) {
+        return errorPlan(Common::Proto::Error_not_enough_utxos);
+    }
+    return plans;
+}
+
 } // namespace TW::Bitcoin
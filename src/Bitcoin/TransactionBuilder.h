@@ -16,6 +16,7 @@
 
 #include <optional>
 #include <algorithm>
+#include <vector>
 
 namespace TW::Bitcoin {
 
@@ -24,12 +25,32 @@ public:
     /// Plans a transaction by selecting UTXOs and calculating fees.
     static TransactionPlan plan(const SigningInput& input);
 
-    /// Builds a transaction with the selected input UTXOs, and one main output and an optional change output.
+    /// Plans a payment split into several independent transactions (merge avoidance).
+    /// UTXOs are grouped by their locking script, and each transaction spends from one group only,
+    /// so that the sender's addresses are not linked together in one transaction.
+    /// The amounts of the returned plans add up to the requested amount, none of them is dust.
+    /// Each transaction gets its own change address (see splitPaymentChangeAddress).
+    /// Payments with extra outputs or OP_RETURN data are not split, a single plan is returned for them.
+    /// Subtracting the fee from the amount is not supported (Error_invalid_params).
+    /// In case of insufficient funds or missing change addresses a single plan with an error is returned.
+    static std::vector<TransactionPlan> planSplitPayment(const SigningInput& input);
+
+    /// Change address of the transaction with the given index in a split payment: the matching entry of
+    /// `splitChangeAddresses`, or `changeAddress` if that is empty and this is the only transaction; empty if none.
+    static std::string splitPaymentChangeAddress(const SigningInput& input, std::size_t index);
+
+    /// Builds a transaction with the selected input UTXOs, and one main output, optional additional outputs and optional change output(s).
     template <typename Transaction>
     static Result<Transaction, Common::Proto::SigningError> build(const TransactionPlan& plan, const std::string& toAddress,
                              const std::string& changeAddress, enum TWCoinType coin, uint32_t lockTime) {
+        if (!isValidVersion(plan.version, coin)) {
+            return Result<Transaction, Common::Proto::SigningError>::failure(Common::Proto::Error_invalid_params);
+        }
         Transaction tx;
         tx.lockTime = lockTime;
+        if (plan.version != 0) {
+            tx._version = static_cast<decltype(tx._version)>(plan.version);
+        }
 
         auto outputTo = prepareOutputWithScript(toAddress, plan.amount, coin);
         if (!outputTo.has_value()) {
@@ -37,8 +58,19 @@ public:
         }
         tx.outputs.push_back(outputTo.value());
 
-        if (plan.change > 0) {
-            auto outputChange = prepareOutputWithScript(changeAddress, plan.change, coin);
+        for (auto& extra : plan.extraOutputs) {
+            auto outputExtra = prepareOutputWithScript(extra.first, extra.second, coin);
+            if (!outputExtra.has_value()) {
+                return Result<Transaction, Common::Proto::SigningError>::failure(Common::Proto::Error_invalid_address);
+            }
+            tx.outputs.push_back(outputExtra.value());
+        }
+
+        if (!isValidChangeSplit(plan)) {
+            return Result<Transaction, Common::Proto::SigningError>::failure(Common::Proto::Error_invalid_params);
+        }
+        for (auto changeAmount : plan.changeOutputAmounts()) {
+            auto outputChange = prepareOutputWithScript(changeAddress, changeAmount, coin);
             if (!outputChange.has_value()) {
                 return Result<Transaction, Common::Proto::SigningError>::failure(Common::Proto::Error_invalid_address);
             }
@@ -47,12 +79,21 @@ public:
 
         const auto emptyScript = Script();
         for (auto& utxo : plan.utxos) {
+            if (utxo.isImmatureCoinbase()) {
+                // spending it would be consensus-invalid
+                return Result<Transaction, Common::Proto::SigningError>::failure(Common::Proto::Error_immature_coinbase);
+            }
             tx.inputs.emplace_back(utxo.outPoint, emptyScript, utxo.outPoint.sequence);
         }
 
-        // Optional OP_RETURN output
+        // Optional OP_RETURN output(s)
+        std::vector<Data> opReturns;
         if (plan.outputOpReturn.size() > 0) {
-            auto lockingScriptOpReturn = Script::buildOpReturnScript(plan.outputOpReturn);
+            opReturns.push_back(plan.outputOpReturn);
+        }
+        opReturns.insert(opReturns.end(), plan.extraOpReturns.begin(), plan.extraOpReturns.end());
+        for (auto& opReturn : opReturns) {
+            auto lockingScriptOpReturn = Script::buildOpReturnScript(opReturn, plan.maxOpReturnLength);
             if (lockingScriptOpReturn.bytes.size() == 0) {
                 return Result<Transaction, Common::Proto::SigningError>::failure(Common::Proto::Error_invalid_memo);
             }
@@ -62,11 +103,35 @@ public:
         return Result<Transaction, Common::Proto::SigningError>(tx);
     }
 
+    /// Whether the randomized change amounts of the plan (if any) are positive and add up to the change.
+    static bool isValidChangeSplit(const TransactionPlan& plan);
+
+    /// Smallest value of an output with the given locking script which is relayed (Bitcoin Core's GetDustThreshold):
+    /// the cost of the output and of a typical input spending it, at the dust relay fee rate.
+    /// E.g. 546 for P2PKH, 294 for P2WPKH; 0 for OP_RETURN.
+    static Amount dustThreshold(const Script& lockingScript);
+
+    /// Whether the transaction version can be used on the given chain (0 means the default, always valid).
+    static bool isValidVersion(int32_t version, enum TWCoinType coin);
+
     /// Prepares a TransactionOutput with given address and amount, prepares script for it
     static std::optional<TransactionOutput> prepareOutputWithScript(std::string address, Amount amount, enum TWCoinType coin);
 
     /// The maximum number of UTXOs to consider.  UTXOs above this limit are cut off because it cak take very long.
     static const size_t MaxUtxosHardLimit;
+
+    /// Mempool chain limits (Bitcoin Core default policy): maximum number of unconfirmed ancestors
+    /// of a transaction (including itself), and their maximum total virtual size.
+    /// UTXOs whose ancestors already reach these are not used, as spending them would be rejected.
+    /// If the selected UTXOs together exceed them, only confirmed UTXOs are used.
+    static const uint32_t MaxAncestorCount;
+    static const uint64_t MaxAncestorSize;
+
+    /// Dust relay fee rate (Bitcoin Core default policy), in satoshi per virtual byte
+    static const int64_t DustRelayFee;
+
+    /// How far ahead of the current block height a lock-time may be, about a week
+    static const uint32_t MaxLockTimeAheadBlocks;
 };
 
 } // namespace TW::Bitcoin
@@ -7,10 +7,16 @@
 #pragma once
 
 #include "Amount.h"
+#include "Script.h"
 #include "UTXO.h"
 #include "Data.h"
 #include "../proto/Bitcoin.pb.h"
 
+#include <algorithm>
+#include <string>
+#include <utility>
+#include <vector>
+
 namespace TW::Bitcoin {
 
 /// Describes a preliminary transaction plan.
@@ -35,6 +41,30 @@ struct TransactionPlan {
 
     Data outputOpReturn;
 
+    /// Number of outputs the change is split into.
+    uint32_t changeOutputCount = 1;
+
+    /// Amounts of the change outputs if they are randomized, empty for an even split.
+    std::vector<Amount> changeAmounts;
+
+    /// Destination/change addresses which have been used before.
+    std::vector<std::string> reusedAddresses;
+
+    /// Additional outputs (address, amount).
+    std::vector<std::pair<std::string, Amount>> extraOutputs;
+
+    /// Destination addresses with a not yet defined witness version (anyone-can-spend).
+    std::vector<std::string> futureWitnessAddresses;
+
+    /// Additional OP_RETURN outputs.
+    std::vector<Data> extraOpReturns;
+
+    /// Maximum length of OP_RETURN data.
+    uint32_t maxOpReturnLength = Script::MaxOpReturnLength;
+
+    /// Transaction version, 0 for the default.
+    int32_t version = 0;
+
     Common::Proto::SigningError error = Common::Proto::SigningError::OK;
 
     TransactionPlan() = default;
@@ -47,8 +77,21 @@ struct TransactionPlan {
         , utxos(std::vector<UTXO>(plan.utxos().begin(), plan.utxos().end()))
         , branchId(plan.branch_id().begin(), plan.branch_id().end())
         , outputOpReturn(plan.output_op_return().begin(), plan.output_op_return().end())
+        , changeOutputCount(std::max(1u, plan.change_output_count()))
+        , changeAmounts(plan.change_amounts().begin(), plan.change_amounts().end())
+        , reusedAddresses(plan.reused_addresses().begin(), plan.reused_addresses().end())
+        , futureWitnessAddresses(plan.future_witness_addresses().begin(), plan.future_witness_addresses().end())
+        , maxOpReturnLength(plan.max_op_return_length() > 0 ? plan.max_op_return_length() : Script::MaxOpReturnLength)
+        , version(plan.version())
         , error(plan.error())
-    {}
+    {
+        for (auto& output: plan.extra_outputs()) {
+            extraOutputs.emplace_back(output.to_address(), output.amount());
+        }
+        for (auto& opReturn: plan.extra_op_returns()) {
+            extraOpReturns.emplace_back(opReturn.begin(), opReturn.end());
+        }
+    }
 
     Proto::TransactionPlan proto() const {
         auto plan = Proto::TransactionPlan();
@@ -61,9 +104,44 @@ struct TransactionPlan {
         }
         plan.set_branch_id(branchId.data(), branchId.size());
         plan.set_output_op_return(outputOpReturn.data(), outputOpReturn.size());
+        plan.set_change_output_count(changeOutputCount);
+        for (auto amount: changeAmounts) {
+            plan.add_change_amounts(amount);
+        }
+        for (auto& address: reusedAddresses) {
+            *plan.add_reused_addresses() = address;
+        }
+        for (auto& output: extraOutputs) {
+            auto* extraOutput = plan.add_extra_outputs();
+            extraOutput->set_to_address(output.first);
+            extraOutput->set_amount(output.second);
+        }
+        for (auto& address: futureWitnessAddresses) {
+            *plan.add_future_witness_addresses() = address;
+        }
+        for (auto& opReturn: extraOpReturns) {
+            plan.add_extra_op_returns(opReturn.data(), opReturn.size());
+        }
+        plan.set_max_op_return_length(maxOpReturnLength);
+        plan.set_version(version);
         plan.set_error(error);
         return plan;
     }
+
+    /// Amounts of the change outputs: the randomized amounts if set, otherwise the change split evenly,
+    /// the first output taking the remainder.  Empty if there is no change.
+    std::vector<Amount> changeOutputAmounts() const {
+        if (change <= 0) {
+            return {};
+        }
+        if (!changeAmounts.empty()) {
+            return changeAmounts;
+        }
+        const auto count = std::max(uint32_t(1), changeOutputCount);
+        std::vector<Amount> amounts(count, change / count);
+        amounts[0] += change % count;
+        return amounts;
+    }
 };
 
 } // namespace TW::Bitcoin
@@ -12,6 +12,7 @@
 #include "../Zcash/Transaction.h"
 #include "../Zcash/TransactionBuilder.h"
 
+
 namespace TW::Bitcoin {
 
 template <typename Transaction, typename TransactionBuilder>
@@ -36,11 +37,16 @@ Result<Transaction, Common::Proto::SigningError> TransactionSigner<Transaction,
         estimationMode ? SigningMode_SizeEstimationOnly : optionalExternalSigs.has_value() ? SigningMode_External
                                                                                            : SigningMode_Normal;
     SignatureBuilder<Transaction> signer(std::move(input), plan, transaction, signingMode, optionalExternalSigs);
-    return signer.sign();
+    auto signResult = signer.sign();
+    if (signResult && !estimationMode && !matchesPlan(signResult.payload(), plan, input)) {
+        // defense in depth: never release a transaction which differs from the approved plan
+        return Result<Transaction, Common::Proto::SigningError>::failure(Common::Proto::Error_plan_mismatch);
+    }
+    return signResult;
 }
 
 template <typename Transaction, typename TransactionBuilder>
-Result<HashPubkeyList, Common::Proto::SigningError> TransactionSigner<Transaction, TransactionBuilder>::preImageHashes(const SigningInput& input) {
+Result<PreImageHashes, Common::Proto::SigningError> TransactionSigner<Transaction, TransactionBuilder>::preImageHashes(const SigningInput& input) {
     TransactionPlan plan;
     if (input.plan.has_value()) {
         plan = input.plan.value();
@@ -49,15 +55,59 @@ Result<HashPubkeyList, Common::Proto::SigningError> TransactionSigner<Transactio
     }
     auto tx_result = TransactionBuilder::template build<Transaction>(plan, input.toAddress, input.changeAddress, input.coinType, input.lockTime);
     if (!tx_result) {
-        return Result<HashPubkeyList, Common::Proto::SigningError>::failure(tx_result.error());
+        return Result<PreImageHashes, Common::Proto::SigningError>::failure(tx_result.error());
     }
     Transaction transaction = tx_result.payload();
     SignatureBuilder<Transaction> signer(std::move(input), plan, transaction, SigningMode_HashOnly);
     auto signResult = signer.sign();
     if (!signResult) {
-        return Result<HashPubkeyList, Common::Proto::SigningError>::failure(signResult.error());
+        return Result<PreImageHashes, Common::Proto::SigningError>::failure(signResult.error());
     }
-    return Result<HashPubkeyList, Common::Proto::SigningError>::success(signer.getHashesForSigning());
+    return Result<PreImageHashes, Common::Proto::SigningError>::success(PreImageHashes{signer.getHashesForSigning(), signer.getPreImagesForSigning()});
+}
+
+template <typename Transaction, typename TransactionBuilder>
+bool TransactionSigner<Transaction, TransactionBuilder>::matchesPlan(const Transaction& transaction, const TransactionPlan& plan, const SigningInput& input) {
+    if (transaction.inputs.size() != plan.utxos.size()) {
+        return false;
+    }
+    for (auto i = 0ul; i < plan.utxos.size(); ++i) {
+        const auto& outPoint = transaction.inputs[i].previousOutput;
+        if (outPoint.hash != plan.utxos[i].outPoint.hash || outPoint.index != plan.utxos[i].outPoint.index) {
+            return false;
+        }
+    }
+
+    // expected outputs, in the order the builder adds them
+    std::vector<TransactionOutput> expected;
+    expected.emplace_back(plan.amount, Script::lockScriptForAddress(input.toAddress, input.coinType));
+    for (auto& extra : plan.extraOutputs) {
+        expected.emplace_back(extra.second, Script::lockScriptForAddress(extra.first, input.coinType));
+    }
+    for (auto changeAmount : plan.changeOutputAmounts()) {
+        expected.emplace_back(changeAmount, Script::lockScriptForAddress(input.changeAddress, input.coinType));
+    }
+    if (!plan.outputOpReturn.empty()) {
+        expected.emplace_back(0, Script::buildOpReturnScript(plan.outputOpReturn, plan.maxOpReturnLength));
+    }
+    for (auto& opReturn : plan.extraOpReturns) {
+        expected.emplace_back(0, Script::buildOpReturnScript(opReturn, plan.maxOpReturnLength));
+    }
+
+    if (transaction.outputs.size() != expected.size()) {
+        return false;
+    }
+    Amount outputSum = 0;
+    for (auto i = 0ul; i < expected.size(); ++i) {
+        const auto& output = transaction.outputs[i];
+        if (expected[i].script.empty() || output.script.bytes != expected[i].script.bytes || output.value != expected[i].value) {
+            return false;
+        }
+        outputSum += output.value;
+    }
+
+    const auto fee = static_cast<Amount>(InputSelector<UTXO>::sum(plan.utxos)) - outputSum;
+    return fee == plan.fee;
 }
 
 // Explicitly instantiate a Signers for compatible transactions.
@@ -22,6 +22,12 @@
 
 namespace TW::Bitcoin {
 
+/// Pre-image hashes to be signed (with public key hashes), and the pre-images themselves, in the same order
+struct PreImageHashes {
+    HashPubkeyList hashes;
+    std::vector<Data> preImages;
+};
+
 /// Frontend class for transaction planning, building, and signing
 template <typename Transaction, typename TransactionBuilder>
 class TransactionSigner {
@@ -32,8 +38,13 @@ public:
     // Sign an unsigned transaction.  Plan it if needed beforehand.
     static Result<Transaction, Common::Proto::SigningError> sign(const SigningInput& input, bool estimationMode = false, std::optional<SignaturePubkeyList> optionalExternalSigs = {});
 
-    /// Collect pre-image hashes to be signed
-    static Result<HashPubkeyList, Common::Proto::SigningError> preImageHashes(const SigningInput& input);
+    /// Collect pre-image hashes to be signed, and the pre-images
+    static Result<PreImageHashes, Common::Proto::SigningError> preImageHashes(const SigningInput& input);
+
+    /// Checks the signed transaction against the plan it was built from: same inputs as the selected UTXOs,
+    /// and exactly the planned outputs (script and value of the destination, extra, change and OP_RETURN outputs),
+    /// and the fee (UTXO amounts minus outputs) exactly equal to the planned one.
+    static bool matchesPlan(const Transaction& transaction, const TransactionPlan& plan, const SigningInput& input);
 };
 
 } // namespace TW::Bitcoin
@@ -7,6 +7,7 @@
 #pragma once
 
 #include "OutPoint.h"
+#include "RelativeLockTime.h"
 #include "Script.h"
 #include "Amount.h"
 #include "../proto/Bitcoin.pb.h"
@@ -26,6 +27,21 @@ public:
     // Amount of the UTXO
     Amount amount;
 
+    // Number of confirmations, 0 for unconfirmed
+    uint32_t confirmations = 0;
+
+    // Number of unconfirmed ancestor transactions (including the parent), 0 if confirmed
+    uint32_t ancestorCount = 0;
+
+    // Total virtual size of the unconfirmed ancestors
+    uint64_t ancestorSize = 0;
+
+    // Whether this is an output of a coinbase transaction
+    bool isCoinbase = false;
+
+    /// Number of confirmations needed before a coinbase output can be spent
+    static constexpr uint32_t CoinbaseMaturity = 100;
+
 public:
     UTXO() = default;
 
@@ -33,6 +49,10 @@ public:
         : outPoint(utxo.out_point())
         , script(utxo.script().begin(), utxo.script().end())
         , amount(utxo.amount())
+        , confirmations(utxo.confirmations())
+        , ancestorCount(utxo.ancestor_count())
+        , ancestorSize(utxo.ancestor_size())
+        , isCoinbase(utxo.is_coinbase())
         {}
 
     Proto::UnspentTransaction proto() const {
@@ -40,8 +60,23 @@ public:
         *utxo.mutable_out_point() = outPoint.proto();
         utxo.set_script(std::string(script.bytes.begin(), script.bytes.end()));
         utxo.set_amount(amount);
+        utxo.set_confirmations(confirmations);
+        utxo.set_ancestor_count(ancestorCount);
+        utxo.set_ancestor_size(ancestorSize);
+        utxo.set_is_coinbase(isCoinbase);
         return utxo;
     }
+
+    /// A coinbase output which cannot be spent yet
+    bool isImmatureCoinbase() const { return isCoinbase && confirmations < CoinbaseMaturity; }
+
+    /// Whether the block-based relative lock-time in the sequence (BIP68, for transaction version 2 or higher)
+    /// is not reached yet by the confirmations
+    bool isRelativeLocked(int32_t transactionVersion) const {
+        const auto relative = RelativeLockTime::fromSequence(outPoint.sequence);
+        return transactionVersion >= 2 && relative.has_value() && relative->unit == RelativeLockTime::Blocks &&
+               confirmations < relative->value;
+    }
 };
 
 /// A list of UTXO's
@@ -42,6 +42,9 @@ Proto::SigningOutput Signer::sign(const Bitcoin::Proto::SigningInput& input) noe
 }
 
 Result<Transaction, Common::Proto::SigningError> Signer::sign() {
+    if (txPlan.error != Common::Proto::OK) {
+        return Result<Transaction, Common::Proto::SigningError>::failure(txPlan.error);
+    }
     if (txPlan.utxos.empty() || _transaction.inputs.empty()) {
         return Result<Transaction, Common::Proto::SigningError>::failure(Common::Proto::Error_missing_input_utxos);
     }
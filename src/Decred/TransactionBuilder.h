@@ -20,7 +20,13 @@ namespace TW::Decred {
 
 struct TransactionBuilder {
     /// Plans a transaction by selecting UTXOs and calculating fees.
+    /// Additional OP_RETURN outputs and a transaction version are not supported, planning fails if they are set.
     static Bitcoin::TransactionPlan plan(const Bitcoin::Proto::SigningInput& input) {
+        if (input.extra_op_returns_size() > 0 || input.version() != 0) {
+            Bitcoin::TransactionPlan plan;
+            plan.error = Common::Proto::Error_invalid_params;
+            return plan;
+        }
         return Bitcoin::TransactionBuilder::plan(input);
     }
 
@@ -32,14 +38,26 @@ struct TransactionBuilder {
         if (lockingScriptTo.empty()) {
             return {};
         }
+        if (!plan.extraOpReturns.empty() || plan.version != 0 || !Bitcoin::TransactionBuilder::isValidChangeSplit(plan)) {
+            // not supported
+            return {};
+        }
 
         Transaction tx;
         tx.outputs.emplace_back(TransactionOutput(plan.amount, /* version: */ 0, lockingScriptTo));
 
-        if (plan.change > 0) {
-            auto lockingScriptChange = Bitcoin::Script::lockScriptForAddress(changeAddress, coin);
+        for (auto& extra : plan.extraOutputs) {
+            auto lockingScriptExtra = Bitcoin::Script::lockScriptForAddress(extra.first, coin);
+            if (lockingScriptExtra.empty()) {
+                return {};
+            }
+            tx.outputs.emplace_back(TransactionOutput(extra.second, /* version: */ 0, lockingScriptExtra));
+        }
+
+        const auto lockingScriptChange = Bitcoin::Script::lockScriptForAddress(changeAddress, coin);
+        for (auto changeAmount : plan.changeOutputAmounts()) {
             tx.outputs.emplace_back(
-                TransactionOutput(plan.change, /* version: */ 0, lockingScriptChange));
+                TransactionOutput(changeAmount, /* version: */ 0, lockingScriptChange));
         }
 
         const auto emptyScript = Bitcoin::Script();
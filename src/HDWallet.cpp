@@ -9,6 +9,7 @@
 #include "Base58.h"
 #include "BinaryCoding.h"
 #include "Bitcoin/CashAddress.h"
+#include "Bitcoin/Script.h"
 #include "Bitcoin/SegwitAddress.h"
 #include "Coin.h"
 #include "ImmutableX/StarkKey.h"
@@ -26,7 +27,11 @@
 #include <TrezorCrypto/curves.h>
 
 #include <array>
+#include <atomic>
 #include <cstring>
+#include <functional>
+#include <system_error>
+#include <thread>
 
 using namespace TW;
 
@@ -36,6 +41,8 @@ uint32_t fingerprint(HDNode* node, Hash::Hasher hasher);
 std::string serialize(const HDNode* node, uint32_t fingerprint, uint32_t version, bool use_public, Hash::Hasher hasher);
 bool deserialize(const std::string& extended, TWCurve curve, Hash::Hasher hasher, HDNode* node);
 const char* curveName(TWCurve curve);
+std::optional<PublicKey> publicKeyFromNode(const HDNode& node, TWCurve curve, TWPublicKeyType keyType);
+bool parallelFor(std::size_t count, const std::function<bool(std::size_t)>& work);
 } // namespace
 
 const int MnemonicBufLength = Mnemonic::MaxWords * (BIP39_MAX_WORD_LENGTH + 3) + 20; // some extra slack
@@ -283,23 +290,66 @@ std::optional<PublicKey> HDWallet<seedSize>::getPublicKeyFromExtended(const std:
 
     // These public key type are not applicable.  Handled above, as node.curve->params is null
     assert(curve != TWCurveED25519 && curve != TWCurveED25519Blake2bNano && curve != TWCurveED25519ExtendedCardano && curve != TWCurveCurve25519);
-    TWPublicKeyType keyType = TW::publicKeyType(coin);
-    if (curve == TWCurveSECP256k1) {
-        auto pubkey = PublicKey(Data(node.public_key, node.public_key + 33), TWPublicKeyTypeSECP256k1);
-        if (keyType == TWPublicKeyTypeSECP256k1Extended) {
-            return pubkey.extended();
-        } else {
-            return pubkey;
-        }
-    } else if (curve == TWCurveNIST256p1) {
-        auto pubkey = PublicKey(Data(node.public_key, node.public_key + 33), TWPublicKeyTypeNIST256p1);
-        if (keyType == TWPublicKeyTypeNIST256p1Extended) {
-            return pubkey.extended();
-        } else {
-            return pubkey;
+    return publicKeyFromNode(node, curve, TW::publicKeyType(coin));
+}
+
+template <std::size_t seedSize>
+std::vector<PublicKey> HDWallet<seedSize>::getPublicKeysFromExtended(const std::string& extended, TWCoinType coin, uint32_t change, uint32_t startIndex, uint32_t count) {
+    const auto curve = TW::curve(coin);
+    const auto hasher = TW::base58Hasher(coin);
+
+    // only non-hardened indices can be derived from a public key
+    if (count > maxPublicKeysFromExtended || change >= 0x80000000 || startIndex >= 0x80000000 || count > 0x80000000 - startIndex) {
+        return {};
+    }
+    auto changeNode = HDNode{};
+    if (!deserialize(extended, curve, hasher, &changeNode)) {
+        return {};
+    }
+    if (changeNode.curve->params == nullptr) {
+        return {};
+    }
+    // the change level is derived only once, shared by all addresses
+    if (hdnode_public_ckd(&changeNode, change) != 1) {
+        return {};
+    }
+
+    const auto keyType = TW::publicKeyType(coin);
+    std::vector<std::optional<PublicKey>> derived(count);
+    const auto success = parallelFor(count, [&](std::size_t i) {
+        auto node = changeNode;
+        if (hdnode_public_ckd(&node, startIndex + static_cast<uint32_t>(i)) != 1) {
+            // invalid child key (probability below 2^-127), no gaps in the returned range
+            return false;
         }
+        hdnode_fill_public_key(&node);
+        derived[i] = publicKeyFromNode(node, curve, keyType);
+        return derived[i].has_value();
+    });
+    if (!success) {
+        return {};
     }
-    return {};
+    std::vector<PublicKey> publicKeys;
+    publicKeys.reserve(count);
+    for (auto& publicKey : derived) {
+        publicKeys.push_back(std::move(publicKey.value()));
+    }
+    return publicKeys;
+}
+
+template <std::size_t seedSize>
+std::vector<Data> HDWallet<seedSize>::getLockScriptsFromExtended(const std::string& extended, TWCoinType coin, TWDerivation derivation, uint32_t change, uint32_t startIndex, uint32_t count) {
+    const auto publicKeys = getPublicKeysFromExtended(extended, coin, change, startIndex, count);
+    std::vector<Data> scripts(publicKeys.size());
+    const auto success = parallelFor(publicKeys.size(), [&](std::size_t i) {
+        const auto address = TW::deriveAddress(coin, publicKeys[i], derivation);
+        scripts[i] = Bitcoin::Script::lockScriptForAddress(address, coin).bytes;
+        return !scripts[i].empty();
+    });
+    if (!success) {
+        return {};
+    }
+    return scripts;
 }
 
 template <std::size_t seedSize>
@@ -382,6 +432,64 @@ bool deserialize(const std::string& extended, TWCurve curve, Hash::Hasher hasher
     return true;
 }
 
+std::optional<PublicKey> publicKeyFromNode(const HDNode& node, TWCurve curve, TWPublicKeyType keyType) {
+    if (curve == TWCurveSECP256k1) {
+        auto pubkey = PublicKey(Data(node.public_key, node.public_key + 33), TWPublicKeyTypeSECP256k1);
+        if (keyType == TWPublicKeyTypeSECP256k1Extended) {
+            return pubkey.extended();
+        } else {
+            return pubkey;
+        }
+    } else if (curve == TWCurveNIST256p1) {
+        auto pubkey = PublicKey(Data(node.public_key, node.public_key + 33), TWPublicKeyTypeNIST256p1);
+        if (keyType == TWPublicKeyTypeNIST256p1Extended) {
+            return pubkey.extended();
+        } else {
+            return pubkey;
+        }
+    }
+    return {};
+}
+
+bool parallelFor(std::size_t count, const std::function<bool(std::size_t)>& work) {
+    // below this, starting threads costs more than it saves
+    const std::size_t minItemsPerThread = 128;
+    const auto threadCount = std::min<std::size_t>(std::max(1u, std::thread::hardware_concurrency()), (count + minItemsPerThread - 1) / minItemsPerThread);
+    const auto chunkSize = threadCount > 1 ? (count + threadCount - 1) / threadCount : count;
+
+    std::atomic<bool> success{true};
+    const auto run = [&](std::size_t begin, std::size_t end) {
+        for (auto i = begin; i < end && success; ++i) {
+            // an exception must not escape a thread
+            try {
+                if (!work(i)) {
+                    success = false;
+                }
+            } catch (...) {
+                success = false;
+            }
+        }
+    };
+
+    // the calling thread takes the first chunk, the others get a thread each
+    std::vector<std::thread> threads;
+    threads.reserve(threadCount);
+    std::size_t chunk = 1;
+    try {
+        for (; chunk < threadCount; ++chunk) {
+            threads.emplace_back(run, chunk * chunkSize, std::min(count, (chunk + 1) * chunkSize));
+        }
+    } catch (const std::system_error&) {
+        // threads are not available (e.g. WebAssembly without pthreads), the rest is done here
+        run(chunk * chunkSize, count);
+    }
+    run(0, std::min(count, chunkSize));
+    for (auto& thread : threads) {
+        thread.join();
+    }
+    return success;
+}
+
 const char* curveName(TWCurve curve) {
     switch (curve) {
     case TWCurveStarkex:
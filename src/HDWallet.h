@@ -21,6 +21,7 @@
 #include <array>
 #include <optional>
 #include <string>
+#include <vector>
 
 namespace TW {
 
@@ -30,6 +31,7 @@ class HDWallet {
     static constexpr size_t mSeedSize = seedSize;
     static constexpr size_t maxMnemomincSize = 240;
     static constexpr size_t maxExtendedKeySize = 128;
+    static constexpr uint32_t maxPublicKeysFromExtended = 10'000;
 
   private:
     /// Wallet seed, derived one-way from the mnemonic and passphrase
@@ -126,6 +128,18 @@ public:
     /// Computes the public key from an extended public key representation.
     static std::optional<PublicKey> getPublicKeyFromExtended(const std::string& extended, TWCoinType coin, const DerivationPath& path);
 
+    /// Computes the public keys of a range of addresses (`change`/`startIndex` ... `change`/`startIndex + count - 1`)
+    /// from an account extended public key. Faster than repeated `getPublicKeyFromExtended` calls,
+    /// as the extended key is parsed and the change level is derived only once, and larger ranges are
+    /// derived on multiple threads; intended for account discovery.
+    /// At most `maxPublicKeysFromExtended` keys can be derived at once.  Returns empty list on error.
+    static std::vector<PublicKey> getPublicKeysFromExtended(const std::string& extended, TWCoinType coin, uint32_t change, uint32_t startIndex, uint32_t count);
+
+    /// Computes the locking scripts (scriptPubKey) of a range of addresses of the given derivation (e.g. legacy or segwit),
+    /// as `getPublicKeysFromExtended`, ready for scanning the chain (e.g. with compact block filters).
+    /// Only for coins with Bitcoin-style scripts.  Returns empty list on error.
+    static std::vector<Data> getLockScriptsFromExtended(const std::string& extended, TWCoinType coin, TWDerivation derivation, uint32_t change, uint32_t startIndex, uint32_t count);
+
     /// Computes the private key from an extended private key representation.
     static std::optional<PrivateKey> getPrivateKeyFromExtended(const std::string& extended, TWCoinType coin, const DerivationPath& path);
 
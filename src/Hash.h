@@ -9,6 +9,7 @@
 #include "Data.h"
 
 #include <functional>
+#include <string>
 
 namespace TW::Hash {
 
@@ -216,6 +217,15 @@ inline Data groestl512d(const byte* data, size_t size) {
     return groestl512(groestl512(data, size));
 }
 
+/// Computes the BIP-340 tagged hash: SHA256(SHA256(tag) || SHA256(tag) || data).
+inline Data sha256Tagged(const std::string& tag, const Data& data) {
+    const auto tagHash = sha256(tag);
+    Data preimage(tagHash);
+    preimage.insert(preimage.end(), tagHash.begin(), tagHash.end());
+    preimage.insert(preimage.end(), data.begin(), data.end());
+    return sha256(preimage);
+}
+
 /// Compute the SHA256-based HMAC of a message
 Data hmac256(const Data& key, const Data& message);
 
@@ -189,6 +189,12 @@ Data Transaction::getSignatureHash(const Bitcoin::Script& scriptCode, size_t ind
     return hash;
 }
 
+Data Transaction::getSignaturePreImage(const Bitcoin::Script& scriptCode, size_t index,
+                                       enum TWBitcoinSigHashType hashType, uint64_t amount,
+                                       [[maybe_unused]] Bitcoin::SignatureVersion version) const {
+    return getPreImage(scriptCode, index, hashType, amount);
+}
+
 Bitcoin::Proto::Transaction Transaction::proto() const {
     auto protoTx = Bitcoin::Proto::Transaction();
     protoTx.set_version(_version);
@@ -68,6 +68,11 @@ struct Transaction {
                           enum TWBitcoinSigHashType hashType, uint64_t amount,
                           enum Bitcoin::SignatureVersion version) const;
 
+    /// Generates the data which is hashed (with BLAKE2b, branch-specific personalization) to obtain the signature hash.
+    Data getSignaturePreImage(const Bitcoin::Script& scriptCode, size_t index,
+                              enum TWBitcoinSigHashType hashType, uint64_t amount,
+                              enum Bitcoin::SignatureVersion version) const;
+
     /// Converts to Protobuf model
     Bitcoin::Proto::Transaction proto() const;
 };
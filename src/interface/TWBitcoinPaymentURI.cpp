@@ -0,0 +1,68 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+#include <TrustWalletCore/TWBitcoinPaymentURI.h>
+
+#include "../Bitcoin/PaymentURI.h"
+#include "../Bitcoin/Script.h"
+
+using namespace TW::Bitcoin;
+
+struct TWBitcoinPaymentURI* _Nullable TWBitcoinPaymentURICreateWithString(TWString* _Nonnull uri, enum TWCoinType coin) {
+    const auto& s = *reinterpret_cast<const std::string*>(uri);
+    auto parsed = PaymentURI::parse(s, coin);
+    if (!parsed.has_value()) {
+        return nullptr;
+    }
+    return new TWBitcoinPaymentURI{ std::move(parsed.value()) };
+}
+
+struct TWBitcoinPaymentURI* _Nullable TWBitcoinPaymentURICreate(TWString* _Nonnull address, int64_t amount, TWString* _Nonnull label, TWString* _Nonnull message, enum TWCoinType coin) {
+    PaymentURI uri;
+    uri.scheme = PaymentURI::schemeForCoin(coin);
+    uri.address = *reinterpret_cast<const std::string*>(address);
+    if (uri.scheme.empty() || amount < 0 || amount > PaymentURI::maxAmount(coin) || Script::lockScriptForAddress(uri.address, coin).empty()) {
+        return nullptr;
+    }
+    if (amount > 0) {
+        uri.amount = amount;
+    }
+    uri.label = *reinterpret_cast<const std::string*>(label);
+    uri.message = *reinterpret_cast<const std::string*>(message);
+    return new TWBitcoinPaymentURI{ uri };
+}
+
+void TWBitcoinPaymentURIDelete(struct TWBitcoinPaymentURI* _Nonnull uri) {
+    delete uri;
+}
+
+TWString* _Nonnull TWBitcoinPaymentURIDescription(const struct TWBitcoinPaymentURI* _Nonnull uri) {
+    return TWStringCreateWithUTF8Bytes(uri->impl.string().c_str());
+}
+
+TWString* _Nonnull TWBitcoinPaymentURIAddress(const struct TWBitcoinPaymentURI* _Nonnull uri) {
+    return TWStringCreateWithUTF8Bytes(uri->impl.address.c_str());
+}
+
+int64_t TWBitcoinPaymentURIAmount(const struct TWBitcoinPaymentURI* _Nonnull uri) {
+    return uri->impl.amount.value_or(0);
+}
+
+TWString* _Nonnull TWBitcoinPaymentURILabel(const struct TWBitcoinPaymentURI* _Nonnull uri) {
+    return TWStringCreateWithUTF8Bytes(uri->impl.label.c_str());
+}
+
+TWString* _Nonnull TWBitcoinPaymentURIMessage(const struct TWBitcoinPaymentURI* _Nonnull uri) {
+    return TWStringCreateWithUTF8Bytes(uri->impl.message.c_str());
+}
+
+TWString* _Nonnull TWBitcoinPaymentURILightning(const struct TWBitcoinPaymentURI* _Nonnull uri) {
+    return TWStringCreateWithUTF8Bytes(uri->impl.lightning.c_str());
+}
+
+TWString* _Nonnull TWBitcoinPaymentURIPayjoin(const struct TWBitcoinPaymentURI* _Nonnull uri) {
+    return TWStringCreateWithUTF8Bytes(uri->impl.payjoin.c_str());
+}
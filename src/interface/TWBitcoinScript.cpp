@@ -118,6 +118,14 @@ TWData *TWBitcoinScriptEncode(const struct TWBitcoinScript *script) {
     return TWDataCreateWithBytes(result.data(), result.size());
 }
 
+TWString *TWBitcoinScriptDisassemble(const struct TWBitcoinScript *script) {
+    return TWStringCreateWithUTF8Bytes(script->impl.disassemble().c_str());
+}
+
+TWString *TWBitcoinScriptExplain(const struct TWBitcoinScript *script) {
+    return TWStringCreateWithUTF8Bytes(script->impl.explain().c_str());
+}
+
 struct TWBitcoinScript *TWBitcoinScriptBuildPayToPublicKey(TWData *pubkey) {
     auto* v = reinterpret_cast<const std::vector<uint8_t>*>(pubkey);
     auto script = TW::Bitcoin::Script::buildPayToPublicKey(*v);
@@ -0,0 +1,25 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+#include <TrustWalletCore/TWBitcoinSplitPayment.h>
+
+#include "Bitcoin/Signer.h"
+#include "Data.h"
+
+using namespace TW;
+
+TWData* _Nonnull TWBitcoinSplitPaymentPlan(TWData* _Nonnull input) {
+    Bitcoin::Proto::SigningInput inputProto;
+
+    const auto bytes = TWDataBytes(input);
+    const auto size = static_cast<int>(TWDataSize(input));
+    if (!inputProto.ParseFromArray(bytes, size)) {
+        return TWDataCreateWithSize(0);
+    }
+
+    const auto output = data(Bitcoin::Signer::planSplitPayment(inputProto).SerializeAsString());
+    return TWDataCreateWithData(&output);
+}
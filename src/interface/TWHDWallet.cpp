@@ -135,3 +135,23 @@ TWPublicKey *TWHDWalletGetPublicKeyFromExtended(TWString *_Nonnull extended, enu
     }
     return new TWPublicKey{ PublicKey(*publicKey) };
 }
+
+TWDataVector *TWHDWalletGetPublicKeysFromExtended(TWString *_Nonnull extended, enum TWCoinType coin, uint32_t change, uint32_t startIndex, uint32_t count) {
+    auto* publicKeys = TWDataVectorCreate();
+    for (const auto& publicKey : HDWallet<>::getPublicKeysFromExtended(*reinterpret_cast<const std::string*>(extended), coin, change, startIndex, count)) {
+        auto* data = TWDataCreateWithBytes(publicKey.bytes.data(), publicKey.bytes.size());
+        TWDataVectorAdd(publicKeys, data);
+        TWDataDelete(data);
+    }
+    return publicKeys;
+}
+
+TWDataVector *TWHDWalletGetLockScriptsFromExtended(TWString *_Nonnull extended, enum TWCoinType coin, enum TWDerivation derivation, uint32_t change, uint32_t startIndex, uint32_t count) {
+    auto* scripts = TWDataVectorCreate();
+    for (const auto& script : HDWallet<>::getLockScriptsFromExtended(*reinterpret_cast<const std::string*>(extended), coin, derivation, change, startIndex, count)) {
+        auto* data = TWDataCreateWithBytes(script.data(), script.size());
+        TWDataVectorAdd(scripts, data);
+        TWDataDelete(data);
+    }
+    return scripts;
+}
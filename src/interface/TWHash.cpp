@@ -28,6 +28,12 @@ TWData* _Nonnull TWHashSHA256(TWData* _Nonnull data) {
     return TWDataCreateWithBytes(result.data(), result.size());
 }
 
+TWData* _Nonnull TWHashSHA256Tagged(TWString* _Nonnull tag, TWData* _Nonnull data) {
+    const auto& tagString = *reinterpret_cast<const std::string*>(tag);
+    const auto result = Hash::sha256Tagged(tagString, *reinterpret_cast<const Data*>(data));
+    return TWDataCreateWithBytes(result.data(), result.size());
+}
+
 TWData* _Nonnull TWHashSHA512(TWData* _Nonnull data) {
     const auto result = Hash::sha512(reinterpret_cast<const byte*>(TWDataBytes(data)), TWDataSize(data));
     return TWDataCreateWithBytes(result.data(), result.size());
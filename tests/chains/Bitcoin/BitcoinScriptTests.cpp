@@ -356,6 +356,88 @@ TEST(BitcoinScript, OpReturnTooLong) {
     EXPECT_EQ(hex(script.bytes), "");
 }
 
+TEST(BitcoinScript, OpReturnCustomLength) {
+    {
+        Data data = Data(100, 0xab);
+        EXPECT_EQ(hex(Script::buildOpReturnScript(data).bytes), "");
+        Script script = Script::buildOpReturnScript(data, 100);
+        EXPECT_EQ(script.bytes.size(), 3 + data.size());
+        EXPECT_EQ(hex(script.bytes), "6a4c64" + hex(data));
+    }
+    {
+        // >255 bytes, with OP_PUSHDATA2
+        Data data = Data(300, 0xab);
+        Script script = Script::buildOpReturnScript(data, 1000);
+        EXPECT_EQ(script.bytes.size(), 4 + data.size());
+        EXPECT_EQ(hex(script.bytes), "6a4d2c01" + hex(data));
+    }
+    {
+        EXPECT_EQ(hex(Script::buildOpReturnScript(Data(1001), 1000).bytes), "");
+        EXPECT_EQ(hex(Script::buildOpReturnScript(Data(0x10000), 0x20000).bytes), "");
+    }
+}
+
+TEST(BitcoinScript, FutureWitnessVersion) {
+    // BIP350 test vectors
+    const auto v1Long = Script::lockScriptForAddress("bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y", TWCoinTypeBitcoin);
+    EXPECT_EQ(hex(v1Long.bytes), "5128751e76e8199196d454941c45d1b3a323f1433bd6751e76e8199196d454941c45d1b3a323f1433bd6");
+    EXPECT_TRUE(v1Long.isFutureWitnessProgram());
+
+    const auto v2 = Script::lockScriptForAddress("bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs", TWCoinTypeBitcoin);
+    EXPECT_EQ(hex(v2.bytes), "5210751e76e8199196d454941c45d1b3a323");
+    EXPECT_TRUE(v2.isFutureWitnessProgram());
+
+    const auto v16 = Script::buildPayToWitnessProgram(16, parse_hex("751e"));
+    EXPECT_EQ(hex(v16.bytes), "6002751e");
+    EXPECT_TRUE(v16.isFutureWitnessProgram());
+
+    const auto taproot = Script::lockScriptForAddress("bc1ptmsk7c2yut2xah4pgflpygh2s7fh0cpfkrza9cjj29awapv53mrslgd5cf", TWCoinTypeBitcoin);
+    EXPECT_EQ(taproot.bytes.size(), 34ul);
+    EXPECT_FALSE(taproot.isFutureWitnessProgram());
+    EXPECT_FALSE(PayToWitnessPublicKeyHash.isFutureWitnessProgram());
+    EXPECT_FALSE(PayToWitnessScriptHash.isFutureWitnessProgram());
+    EXPECT_FALSE(PayToPublicKeyHash.isFutureWitnessProgram());
+}
+
+TEST(BitcoinScript, Disassemble) {
+    EXPECT_EQ(PayToPublicKeyHash.disassemble(), "OP_DUP OP_HASH160 79091972186c449eb1ded22b78e40d009bdf0089 OP_EQUALVERIFY OP_CHECKSIG");
+    EXPECT_EQ(PayToWitnessPublicKeyHash.disassemble(), "0 79091972186c449eb1ded22b78e40d009bdf0089");
+    EXPECT_EQ(Script(parse_hex("6a" "4c05" "abcdef0102")).disassemble(), "OP_RETURN abcdef0102");
+    // pushes of up to 4 bytes are numbers (little endian, sign bit), as in Bitcoin Core
+    EXPECT_EQ(Script(parse_hex("029000" "b2" "75")).disassemble(), "144 OP_CHECKSEQUENCEVERIFY OP_DROP");
+    EXPECT_EQ(Script(parse_hex("0300350c" "b1")).disassemble(), "800000 OP_CHECKLOCKTIMEVERIFY");
+    EXPECT_EQ(Script(parse_hex("0181" "02abcd" "4c00")).disassemble(), "-1 -19883 0");
+    EXPECT_EQ(Script(parse_hex("4f" "60" "b3" "c0")).disassemble(), "-1 16 OP_NOP4 OP_UNKNOWN");
+    EXPECT_EQ(Script(parse_hex("76" "4c05" "0102")).disassemble(), "OP_DUP [error]");
+    EXPECT_EQ(Script().disassemble(), "");
+}
+
+TEST(BitcoinScript, Explain) {
+    EXPECT_EQ(PayToPublicKeyHash.explain(), "P2PKH");
+    EXPECT_EQ(PayToScriptHash.explain(), "P2SH");
+    EXPECT_EQ(PayToWitnessPublicKeyHash.explain(), "P2WPKH");
+    EXPECT_EQ(PayToWitnessScriptHash.explain(), "P2WSH");
+    EXPECT_EQ(PayToPublicKeySecp256k1.explain(), "P2PK");
+    EXPECT_EQ(Script::buildPayToWitnessProgram(2, parse_hex("751e")).explain(), "witness v2 program");
+    EXPECT_EQ(Script::buildPayToWitnessProgram(1, Data(32, 0x11)).explain(), "P2TR");
+    // not P2TR: v1 with another length, v0 with neither 20 nor 32 bytes
+    EXPECT_EQ(Script::buildPayToWitnessProgram(1, Data(20, 0x11)).explain(), "witness v1 program");
+    EXPECT_EQ(Script::buildPayToWitnessProgram(0, Data(16, 0x11)).explain(), "witness v0 program");
+    EXPECT_EQ(Script::buildOpReturnScript(parse_hex("abcd")).explain(), "OP_RETURN 2 bytes");
+
+    const auto pubkey = "03c9f4836b9a4f77fc0d81f7bcb01b7f1b35916864b9476c241ce9fc198bd25432";
+    EXPECT_EQ(Script(parse_hex(std::string("52") + "21" + pubkey + "21" + pubkey + "52" + "ae")).explain(), "2-of-2 CHECKMULTISIG");
+
+    // <144> OP_CHECKSEQUENCEVERIFY OP_DROP <pubkey> OP_CHECKSIG
+    EXPECT_EQ(Script(parse_hex(std::string("029000" "b2" "75" "21") + pubkey + "ac")).explain(), "CSV 144 blocks");
+    // <169 | type flag> OP_CHECKSEQUENCEVERIFY: 169 * 512 seconds
+    EXPECT_EQ(Script(parse_hex("03a90040" "b2" "75")).explain(), "CSV 86528 seconds");
+    // <800000> OP_CHECKLOCKTIMEVERIFY, <1700000000> OP_CHECKLOCKTIMEVERIFY
+    EXPECT_EQ(Script(parse_hex("0300350c" "b1" "75" "0400f15365" "b1" "75")).explain(), "CLTV block 800000, CLTV time 1700000000");
+
+    EXPECT_EQ(Script(parse_hex("76")).explain(), "");
+}
+
 TEST(BitcoinTransactionSigner, PushAllEmpty) {
     {
         std::vector<Data> input = {};
@@ -67,6 +67,33 @@ TEST(BitcoinInputSelector, SelectUnspents5_simple) {
     EXPECT_TRUE(verifySelectedUTXOs(selected, {1000, 2000, 3000, 4000, 5000, 6000, 7000, 8000}));
 }
 
+TEST(BitcoinInputSelector, SelectUnspents5_inOrder) {
+    auto utxos = buildTestUTXOs({1000, 2000, 3000, 4000, 5000, 6000, 7000, 8000, 9000});
+
+    auto selector = InputSelector<UTXO>(utxos);
+    auto selected = selector.selectInOrder(28000, 1);
+
+    EXPECT_TRUE(verifySelectedUTXOs(selected, {1000, 2000, 3000, 4000, 5000, 6000, 7000, 8000}));
+}
+
+TEST(BitcoinInputSelector, SelectInOrderSkipsDust) {
+    auto utxos = buildTestUTXOs({50, 10000, 20000});
+
+    auto selector = InputSelector<UTXO>(utxos);
+    auto selected = selector.selectInOrder(5000, 1);
+
+    EXPECT_TRUE(verifySelectedUTXOs(selected, {10000}));
+}
+
+TEST(BitcoinInputSelector, SelectInOrderInsufficient) {
+    auto utxos = buildTestUTXOs({4000, 4000, 4000});
+
+    auto selector = InputSelector<UTXO>(utxos);
+    auto selected = selector.selectInOrder(12000, 1);
+
+    EXPECT_TRUE(verifySelectedUTXOs(selected, {}));
+}
+
 TEST(BitcoinInputSelector, SelectUnspentsInsufficient) {
     auto utxos = buildTestUTXOs({4000, 4000, 4000});
 
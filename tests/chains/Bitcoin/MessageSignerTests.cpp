@@ -132,11 +132,76 @@ TEST(BitcoinMessageSigner, VerifyNegative) {
         "test signature",
         parse_hex("1fedcbe486d255c7a3a784b65702d70b12c43100160ef29b13c950caad2871dbf23356a812e764ccdfd2fea2c8def9cd38563a575dc15d6485acfaf73a319ae4")
     ), "Input address invalid");
-    EXPECT_EXCEPTION(MessageSigner::verifyMessage(
+    // Segwit address, neither a recoverable nor a BIP322 signature
+    EXPECT_FALSE(MessageSigner::verifyMessage(
         "bc1qpjult34k9spjfym8hss2jrwjgf0xjf40ze0pp8",
         "test signature",
         parse_hex("1fedcbe486d255c7a3a784b65702d70b12c43100160ef29b13c950caad2871dbf23356a812e764ccdfd2fea2c8def9cd38563a575dc15d6485acfaf73a319ae4")
-    ), "Input address invalid");
+    ));
+    // Taproot address
+    EXPECT_EXCEPTION(MessageSigner::verifyMessage(
+        "bc1ptmsk7c2yut2xah4pgflpygh2s7fh0cpfkrza9cjj29awapv53mrslgd5cf",
+        "test signature",
+        sig
+    ), "Only P2WPKH segwit addresses are supported");
+}
+
+TEST(BitcoinMessageSigner, VerifyMessageSegwitRecoverable) {
+    // BIP137 header for P2WPKH
+    EXPECT_TRUE(MessageSigner::verifyMessage(
+        "bc1qten42eesehw0ktddcp0fws7d3ycsqez3f7d5yt",
+        "Hello World",
+        "J7tQ4tiaTtcGY9CAZZ/grUubw+BsF6InQzlmy1nO7gINc6vWpL0phl+ZNniSBaJuX8ecgGOBwxCNLReupeC2IEA="
+    ));
+    // BIP137 header for P2SH-P2WPKH
+    EXPECT_TRUE(MessageSigner::verifyMessage(
+        "3F4bWvE6GDANhDX9tBzGZzv3KvE7YH82ev",
+        "Hello World",
+        "I7tQ4tiaTtcGY9CAZZ/grUubw+BsF6InQzlmy1nO7gINc6vWpL0phl+ZNniSBaJuX8ecgGOBwxCNLReupeC2IEA="
+    ));
+    // the same key as P2PKH
+    const auto p2pkh = Address(gPrivateKey.getPublicKey(TWPublicKeyTypeSECP256k1), TW::p2pkhPrefix(TWCoinTypeBitcoin)).string();
+    EXPECT_TRUE(MessageSigner::verifyMessage(
+        p2pkh,
+        "Hello World",
+        "J7tQ4tiaTtcGY9CAZZ/grUubw+BsF6InQzlmy1nO7gINc6vWpL0phl+ZNniSBaJuX8ecgGOBwxCNLReupeC2IEA="
+    ));
+    EXPECT_FALSE(MessageSigner::verifyMessage(
+        "bc1qten42eesehw0ktddcp0fws7d3ycsqez3f7d5yt",
+        "Hello World!",
+        "J7tQ4tiaTtcGY9CAZZ/grUubw+BsF6InQzlmy1nO7gINc6vWpL0phl+ZNniSBaJuX8ecgGOBwxCNLReupeC2IEA="
+    ));
+}
+
+TEST(BitcoinMessageSigner, VerifyMessageBip322) {
+    // BIP322 test vectors
+    EXPECT_TRUE(MessageSigner::verifyMessage(
+        "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l",
+        "",
+        "AkcwRAIgM2gBAQqvZX15ZiysmKmQpDrG83avLIT492QBzLnQIxYCIBaTpOaD20qRlEylyxFSeEA2ba9YOixpX8z46TSDtS40ASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI="
+    ));
+    EXPECT_TRUE(MessageSigner::verifyMessage(
+        "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l",
+        "Hello World",
+        "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI="
+    ));
+    // wrong message
+    EXPECT_FALSE(MessageSigner::verifyMessage(
+        "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l",
+        "Hello World!",
+        "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI="
+    ));
+    // wrong address
+    EXPECT_FALSE(MessageSigner::verifyMessage(
+        "bc1qten42eesehw0ktddcp0fws7d3ycsqez3f7d5yt",
+        "Hello World",
+        "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI="
+    ));
+}
+
+TEST(BitcoinMessageSigner, MessageToBip322Hash) {
+    EXPECT_EQ(hex(MessageSigner::messageToBip322Hash("")), "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1");
+    EXPECT_EQ(hex(MessageSigner::messageToBip322Hash("Hello World")), "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a");
 }
 
 TEST(BitcoinMessageSigner, MessageToHash) {
@@ -0,0 +1,143 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+#include "Bitcoin/PaymentURI.h"
+#include "Bitcoin/SigningInput.h"
+
+#include <gtest/gtest.h>
+#include <limits>
+
+namespace TW::Bitcoin::tests {
+
+TEST(BitcoinPaymentURI, ParseAddressOnly) {
+    const auto uri = PaymentURI::parse("bitcoin:1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx");
+    ASSERT_TRUE(uri.has_value());
+    EXPECT_EQ(uri->scheme, "bitcoin");
+    EXPECT_EQ(uri->address, "1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx");
+    EXPECT_FALSE(uri->amount.has_value());
+    EXPECT_EQ(uri->label, "");
+    EXPECT_EQ(uri->message, "");
+}
+
+TEST(BitcoinPaymentURI, ParseParams) {
+    const auto uri = PaymentURI::parse("BITCOIN:bc1q7s0a2l4aguksehx8hf93hs9yggl6njxds6m02g?amount=20.3&label=Luke-Jr&message=Donation%20for%20project%20xyz&pj=https://example.com/pj&somethingelse=x");
+    ASSERT_TRUE(uri.has_value());
+    EXPECT_EQ(uri->scheme, "bitcoin");
+    EXPECT_EQ(uri->address, "bc1q7s0a2l4aguksehx8hf93hs9yggl6njxds6m02g");
+    ASSERT_TRUE(uri->amount.has_value());
+    EXPECT_EQ(uri->amount.value(), 2'030'000'000);
+    EXPECT_EQ(uri->label, "Luke-Jr");
+    EXPECT_EQ(uri->message, "Donation for project xyz");
+    EXPECT_EQ(uri->payjoin, "https://example.com/pj");
+    ASSERT_EQ(uri->otherParams.size(), 1ul);
+    EXPECT_EQ(uri->otherParams.at("somethingelse"), "x");
+}
+
+TEST(BitcoinPaymentURI, ParseLightningOnly) {
+    const auto uri = PaymentURI::parse("bitcoin:?lightning=lnbc10u1p3pj257pp5yztkwjcz5ftl5laxkav23zmzekaw37zk6kmv80pk4xaev5qhtz7q");
+    ASSERT_TRUE(uri.has_value());
+    EXPECT_EQ(uri->address, "");
+    EXPECT_EQ(uri->lightning, "lnbc10u1p3pj257pp5yztkwjcz5ftl5laxkav23zmzekaw37zk6kmv80pk4xaev5qhtz7q");
+
+    SigningInput input;
+    EXPECT_FALSE(uri->applyTo(input));
+}
+
+TEST(BitcoinPaymentURI, ParseInvalid) {
+    // no scheme
+    EXPECT_FALSE(PaymentURI::parse("1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx").has_value());
+    // invalid address
+    EXPECT_FALSE(PaymentURI::parse("bitcoin:1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcy").has_value());
+    // no address, no lightning
+    EXPECT_FALSE(PaymentURI::parse("bitcoin:?amount=1").has_value());
+    // unknown required parameter
+    EXPECT_FALSE(PaymentURI::parse("bitcoin:1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx?req-somethingyoudontunderstand=50").has_value());
+    // duplicate parameter
+    EXPECT_FALSE(PaymentURI::parse("bitcoin:1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx?amount=1&amount=2").has_value());
+    // invalid amounts
+    EXPECT_FALSE(PaymentURI::parse("bitcoin:1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx?amount=1,5").has_value());
+    EXPECT_FALSE(PaymentURI::parse("bitcoin:1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx?amount=0.000000001").has_value());
+    EXPECT_FALSE(PaymentURI::parse("bitcoin:1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx?amount=21000001").has_value());
+    // invalid percent encoding
+    EXPECT_FALSE(PaymentURI::parse("bitcoin:1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx?label=a%2").has_value());
+    // scheme of another coin
+    EXPECT_FALSE(PaymentURI::parse("litecoin:1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx").has_value());
+    EXPECT_FALSE(PaymentURI::parse("bitcoin:ltc1qytnqzjknvv03jwfgrsmzt0ycmwqgl0asjnaxwu", TWCoinTypeLitecoin).has_value());
+}
+
+TEST(BitcoinPaymentURI, OtherCoin) {
+    const auto uri = PaymentURI::parse("litecoin:ltc1qytnqzjknvv03jwfgrsmzt0ycmwqgl0asjnaxwu?amount=1.5", TWCoinTypeLitecoin);
+    ASSERT_TRUE(uri.has_value());
+    EXPECT_EQ(uri->scheme, "litecoin");
+    EXPECT_EQ(uri->amount.value(), 150'000'000);
+    EXPECT_EQ(uri->string(), "litecoin:ltc1qytnqzjknvv03jwfgrsmzt0ycmwqgl0asjnaxwu?amount=1.5");
+
+    EXPECT_EQ(PaymentURI::schemeForCoin(TWCoinTypeDogecoin), "dogecoin");
+    EXPECT_EQ(PaymentURI::schemeForCoin(TWCoinTypeEthereum), "");
+}
+
+TEST(BitcoinPaymentURI, DogecoinAmount) {
+    // above the Bitcoin limit, valid for Dogecoin
+    const auto uri = PaymentURI::parse("dogecoin:DLSSSUS3ex7YNDACJDxMER1ZMW579Vy8Zy?amount=100000000", TWCoinTypeDogecoin);
+    ASSERT_TRUE(uri.has_value());
+    EXPECT_EQ(uri->amount.value(), 10'000'000'000'000'000);
+    EXPECT_EQ(uri->string(), "dogecoin:DLSSSUS3ex7YNDACJDxMER1ZMW579Vy8Zy?amount=100000000");
+
+    EXPECT_EQ(PaymentURI::parseAmount("10000000000", TWCoinTypeDogecoin).value(), 1'000'000'000'000'000'000);
+    EXPECT_FALSE(PaymentURI::parseAmount("10000000000.00000001", TWCoinTypeDogecoin).has_value());
+    EXPECT_FALSE(PaymentURI::parse("dogecoin:DLSSSUS3ex7YNDACJDxMER1ZMW579Vy8Zy?amount=20000000000", TWCoinTypeDogecoin).has_value());
+}
+
+TEST(BitcoinPaymentURI, Amount) {
+    EXPECT_EQ(PaymentURI::parseAmount("1").value(), 100'000'000);
+    EXPECT_EQ(PaymentURI::parseAmount("0.00000001").value(), 1);
+    EXPECT_EQ(PaymentURI::parseAmount(".5").value(), 50'000'000);
+    EXPECT_EQ(PaymentURI::parseAmount("21000000").value(), 2'100'000'000'000'000);
+    EXPECT_FALSE(PaymentURI::parseAmount("").has_value());
+    EXPECT_FALSE(PaymentURI::parseAmount(".").has_value());
+    EXPECT_FALSE(PaymentURI::parseAmount("-1").has_value());
+    EXPECT_FALSE(PaymentURI::parseAmount("1e3").has_value());
+    EXPECT_FALSE(PaymentURI::parseAmount("21000000.00000001").has_value());
+    // no overflow
+    EXPECT_FALSE(PaymentURI::parseAmount("92233720368.54775808", TWCoinTypeEthereum).has_value());
+    EXPECT_FALSE(PaymentURI::parseAmount("100000000000000000000000000000").has_value());
+    EXPECT_EQ(PaymentURI::parseAmount("92233720368.54775807", TWCoinTypeEthereum).value(), std::numeric_limits<Amount>::max());
+    EXPECT_EQ(PaymentURI::parseAmount("84000000", TWCoinTypeLitecoin).value(), PaymentURI::maxAmount(TWCoinTypeLitecoin));
+
+    EXPECT_EQ(PaymentURI::formatAmount(100'000'000), "1");
+    EXPECT_EQ(PaymentURI::formatAmount(2'030'000'000), "20.3");
+    EXPECT_EQ(PaymentURI::formatAmount(1), "0.00000001");
+}
+
+TEST(BitcoinPaymentURI, String) {
+    PaymentURI uri;
+    uri.address = "1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx";
+    EXPECT_EQ(uri.string(), "bitcoin:1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx");
+
+    uri.amount = 150'000;
+    uri.label = "Shop & Co";
+    uri.message = "Order #42";
+    EXPECT_EQ(uri.string(), "bitcoin:1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx?amount=0.0015&label=Shop%20%26%20Co&message=Order%20%2342");
+
+    // round trip
+    const auto parsed = PaymentURI::parse(uri.string());
+    ASSERT_TRUE(parsed.has_value());
+    EXPECT_EQ(parsed->amount.value(), 150'000);
+    EXPECT_EQ(parsed->label, "Shop & Co");
+    EXPECT_EQ(parsed->message, "Order #42");
+}
+
+TEST(BitcoinPaymentURI, ApplyToSigningInput) {
+    const auto uri = PaymentURI::parse("bitcoin:1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx?amount=0.0005");
+    ASSERT_TRUE(uri.has_value());
+
+    SigningInput input;
+    EXPECT_TRUE(uri->applyTo(input));
+    EXPECT_EQ(input.toAddress, "1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx");
+    EXPECT_EQ(input.amount, 50'000);
+}
+
+} // namespace TW::Bitcoin::tests
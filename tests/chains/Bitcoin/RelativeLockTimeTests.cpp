@@ -0,0 +1,64 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+#include "Bitcoin/RelativeLockTime.h"
+
+#include <gtest/gtest.h>
+
+namespace TW::Bitcoin::tests {
+
+TEST(BitcoinRelativeLockTime, Blocks) {
+    const auto lockTime = RelativeLockTime::fromBlocks(144);
+    EXPECT_EQ(lockTime.sequence(), 144u);
+    EXPECT_EQ(lockTime.seconds(), 0u);
+
+    const auto decoded = RelativeLockTime::fromSequence(144);
+    ASSERT_TRUE(decoded.has_value());
+    EXPECT_EQ(decoded->unit, RelativeLockTime::Blocks);
+    EXPECT_EQ(decoded->value, 144);
+}
+
+TEST(BitcoinRelativeLockTime, Seconds) {
+    // one day, rounded up to 512 s units
+    const auto lockTime = RelativeLockTime::fromSeconds(86400);
+    ASSERT_TRUE(lockTime.has_value());
+    EXPECT_EQ(lockTime->unit, RelativeLockTime::Time);
+    EXPECT_EQ(lockTime->value, 169);
+    EXPECT_EQ(lockTime->seconds(), 86528u);
+    EXPECT_EQ(lockTime->sequence(), 0x004000a9u);
+
+    EXPECT_EQ(RelativeLockTime::fromSeconds(512)->value, 1);
+    EXPECT_EQ(RelativeLockTime::fromSeconds(0)->value, 0);
+    EXPECT_EQ(RelativeLockTime::fromSeconds(65535 * 512)->value, 65535);
+    EXPECT_FALSE(RelativeLockTime::fromSeconds(65535 * 512 + 1).has_value());
+}
+
+TEST(BitcoinRelativeLockTime, FromSequence) {
+    // disabled
+    EXPECT_FALSE(RelativeLockTime::fromSequence(0xffffffff).has_value());
+    EXPECT_FALSE(RelativeLockTime::fromSequence(0x80000010).has_value());
+
+    // time based; bits outside of the type flag and value are ignored
+    const auto decoded = RelativeLockTime::fromSequence(0x004100a9);
+    ASSERT_TRUE(decoded.has_value());
+    EXPECT_EQ(decoded->unit, RelativeLockTime::Time);
+    EXPECT_EQ(decoded->value, 169);
+}
+
+TEST(BitcoinRelativeLockTime, CombineRequirements) {
+    const auto combined = RelativeLockTime::combine({RelativeLockTime::fromBlocks(10), RelativeLockTime::fromBlocks(144)});
+    ASSERT_TRUE(combined.has_value());
+    EXPECT_EQ(combined->unit, RelativeLockTime::Blocks);
+    EXPECT_EQ(combined->value, 144);
+    EXPECT_TRUE(combined->satisfies(RelativeLockTime::fromBlocks(10)));
+    EXPECT_FALSE(RelativeLockTime::fromBlocks(10).satisfies(combined.value()));
+
+    // blocks and time cannot be mixed
+    EXPECT_FALSE(RelativeLockTime::combine({RelativeLockTime::fromBlocks(10), RelativeLockTime::fromSeconds(3600).value()}).has_value());
+    EXPECT_FALSE(RelativeLockTime::fromSeconds(3600)->satisfies(RelativeLockTime::fromBlocks(1)));
+}
+
+} // namespace TW::Bitcoin::tests
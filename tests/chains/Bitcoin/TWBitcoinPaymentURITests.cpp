@@ -0,0 +1,53 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+#include "TestUtilities.h"
+
+#include <TrustWalletCore/TWBitcoinPaymentURI.h>
+
+#include <gtest/gtest.h>
+
+TEST(TWBitcoinPaymentURI, Parse) {
+    const auto uri = WRAP(TWBitcoinPaymentURI, TWBitcoinPaymentURICreateWithString(STRING("bitcoin:bc1q7s0a2l4aguksehx8hf93hs9yggl6njxds6m02g?amount=20.3&label=Luke-Jr&message=Donation%20for%20project%20xyz&pj=https://example.com/pj").get(), TWCoinTypeBitcoin));
+    ASSERT_NE(uri.get(), nullptr);
+
+    assertStringsEqual(WRAPS(TWBitcoinPaymentURIAddress(uri.get())), "bc1q7s0a2l4aguksehx8hf93hs9yggl6njxds6m02g");
+    EXPECT_EQ(TWBitcoinPaymentURIAmount(uri.get()), 2'030'000'000);
+    assertStringsEqual(WRAPS(TWBitcoinPaymentURILabel(uri.get())), "Luke-Jr");
+    assertStringsEqual(WRAPS(TWBitcoinPaymentURIMessage(uri.get())), "Donation for project xyz");
+    assertStringsEqual(WRAPS(TWBitcoinPaymentURIPayjoin(uri.get())), "https://example.com/pj");
+    assertStringsEqual(WRAPS(TWBitcoinPaymentURILightning(uri.get())), "");
+}
+
+TEST(TWBitcoinPaymentURI, ParseInvalid) {
+    // scheme of another coin
+    EXPECT_EQ(TWBitcoinPaymentURICreateWithString(STRING("bitcoin:ltc1qytnqzjknvv03jwfgrsmzt0ycmwqgl0asjnaxwu").get(), TWCoinTypeLitecoin), nullptr);
+    EXPECT_EQ(TWBitcoinPaymentURICreateWithString(STRING("litecoin:1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx").get(), TWCoinTypeBitcoin), nullptr);
+    EXPECT_EQ(TWBitcoinPaymentURICreateWithString(STRING("bitcoin:1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcy").get(), TWCoinTypeBitcoin), nullptr);
+}
+
+TEST(TWBitcoinPaymentURI, Create) {
+    const auto uri = WRAP(TWBitcoinPaymentURI, TWBitcoinPaymentURICreate(STRING("ltc1qytnqzjknvv03jwfgrsmzt0ycmwqgl0asjnaxwu").get(), 150'000, STRING("Shop & Co").get(), STRING("").get(), TWCoinTypeLitecoin));
+    ASSERT_NE(uri.get(), nullptr);
+    assertStringsEqual(WRAPS(TWBitcoinPaymentURIDescription(uri.get())), "litecoin:ltc1qytnqzjknvv03jwfgrsmzt0ycmwqgl0asjnaxwu?amount=0.0015&label=Shop%20%26%20Co");
+
+    // round trip
+    const auto parsed = WRAP(TWBitcoinPaymentURI, TWBitcoinPaymentURICreateWithString(WRAPS(TWBitcoinPaymentURIDescription(uri.get())).get(), TWCoinTypeLitecoin));
+    ASSERT_NE(parsed.get(), nullptr);
+    EXPECT_EQ(TWBitcoinPaymentURIAmount(parsed.get()), 150'000);
+    assertStringsEqual(WRAPS(TWBitcoinPaymentURILabel(parsed.get())), "Shop & Co");
+
+    // invalid address, negative amount, coin without scheme
+    EXPECT_EQ(TWBitcoinPaymentURICreate(STRING("1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcy").get(), 0, STRING("").get(), STRING("").get(), TWCoinTypeBitcoin), nullptr);
+    EXPECT_EQ(TWBitcoinPaymentURICreate(STRING("1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx").get(), -1, STRING("").get(), STRING("").get(), TWCoinTypeBitcoin), nullptr);
+    EXPECT_EQ(TWBitcoinPaymentURICreate(STRING("1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx").get(), 0, STRING("").get(), STRING("").get(), TWCoinTypeGroestlcoin), nullptr);
+
+    // amount above the coin's limit
+    EXPECT_EQ(TWBitcoinPaymentURICreate(STRING("1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx").get(), 2'100'000'000'000'001, STRING("").get(), STRING("").get(), TWCoinTypeBitcoin), nullptr);
+    const auto doge = WRAP(TWBitcoinPaymentURI, TWBitcoinPaymentURICreate(STRING("DLSSSUS3ex7YNDACJDxMER1ZMW579Vy8Zy").get(), 10'000'000'000'000'000, STRING("").get(), STRING("").get(), TWCoinTypeDogecoin));
+    ASSERT_NE(doge.get(), nullptr);
+    assertStringsEqual(WRAPS(TWBitcoinPaymentURIDescription(doge.get())), "dogecoin:DLSSSUS3ex7YNDACJDxMER1ZMW579Vy8Zy?amount=100000000");
+}
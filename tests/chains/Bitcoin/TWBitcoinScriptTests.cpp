@@ -242,4 +242,19 @@ TEST(TWBitcoinSigHashType, IsNone) {
     EXPECT_FALSE(TWBitcoinSigHashTypeIsNone(TWBitcoinSigHashTypeFork));
 }
 
+TEST(TWBitcoinScript, DisassembleAndExplain) {
+    assertStringsEqual(WRAPS(TWBitcoinScriptDisassemble(PayToPublicKeyHash.get())), "OP_DUP OP_HASH160 79091972186c449eb1ded22b78e40d009bdf0089 OP_EQUALVERIFY OP_CHECKSIG");
+    assertStringsEqual(WRAPS(TWBitcoinScriptExplain(PayToPublicKeyHash.get())), "P2PKH");
+    assertStringsEqual(WRAPS(TWBitcoinScriptExplain(PayToWitnessScriptHash.get())), "P2WSH");
+
+    // <144> OP_CHECKSEQUENCEVERIFY OP_DROP
+    const auto script = WRAP(TWBitcoinScript, TWBitcoinScriptCreateWithData(DATA("029000" "b2" "75").get()));
+    assertStringsEqual(WRAPS(TWBitcoinScriptDisassemble(script.get())), "144 OP_CHECKSEQUENCEVERIFY OP_DROP");
+    assertStringsEqual(WRAPS(TWBitcoinScriptExplain(script.get())), "CSV 144 blocks");
+
+    const auto empty = WRAP(TWBitcoinScript, TWBitcoinScriptCreate());
+    assertStringsEqual(WRAPS(TWBitcoinScriptDisassemble(empty.get())), "");
+    assertStringsEqual(WRAPS(TWBitcoinScriptExplain(empty.get())), "");
+}
+
 } // namespace TW::Bitcoin::TWScriptTests
@@ -125,6 +125,96 @@ TEST(BitcoinSigning, SignP2PKH_NegativeMissingKey) {
     EXPECT_EQ(result.error(), Common::Proto::Error_missing_private_key);
 }
 
+TEST(BitcoinSigning, SignedTransactionMatchesPlan) {
+    auto input = buildSigningInput(50'000, 1, buildTestUTXOs({100'000}));
+    auto plan = TransactionBuilder::plan(input);
+    EXPECT_TRUE(verifyPlan(plan, {100'000}, 50'000, 147));
+
+    input.plan = plan;
+    auto result = TransactionSigner<Transaction, TransactionBuilder>::sign(input);
+    ASSERT_TRUE(result) << std::to_string(result.error());
+    using TxSigner = TransactionSigner<Transaction, TransactionBuilder>;
+    const auto signedTx = result.payload();
+    EXPECT_TRUE(TxSigner::matchesPlan(signedTx, plan, input));
+
+    // tampered outputs are detected: value, script, an extra output
+    auto tx = signedTx;
+    tx.outputs[1].value -= 1;
+    EXPECT_FALSE(TxSigner::matchesPlan(tx, plan, input));
+    tx = signedTx;
+    tx.outputs[1].script = Script::lockScriptForAddress("bc1q7s0a2l4aguksehx8hf93hs9yggl6njxds6m02g", TWCoinTypeBitcoin);
+    EXPECT_FALSE(TxSigner::matchesPlan(tx, plan, input));
+    tx = signedTx;
+    tx.outputs.emplace_back(0, Script::buildOpReturnScript(parse_hex("00")));
+    EXPECT_FALSE(TxSigner::matchesPlan(tx, plan, input));
+
+    // the fee must match exactly
+    auto offByOnePlan = plan;
+    offByOnePlan.fee += 1;
+    EXPECT_FALSE(TxSigner::matchesPlan(signedTx, offByOnePlan, input));
+
+    // inconsistent plan: the fee actually paid would differ from the approved fee
+    input.plan->fee += 1;
+    result = TxSigner::sign(input);
+    ASSERT_FALSE(result);
+    EXPECT_EQ(result.error(), Common::Proto::Error_plan_mismatch);
+}
+
+TEST(BitcoinSigning, SignP2WSH_MultisigPartialKeys) {
+    const auto key1 = PrivateKey(parse_hex("619c335025c7f4012e556c2a58b2506e30b8511b53ade95ea316fd8c3286feb9"));
+    const auto key2 = PrivateKey(parse_hex("ed00a0841cd53aedf89b0c616742d1d2a930f8ae2b0fb514765a17bb62c7521a"));
+    const auto key3 = PrivateKey(parse_hex("afeefca74d9a325cf1d6b6911d61a65c32afa8e02bd5e78e2e4ac2910bab45f5"));
+    const auto pubKey2 = key2.getPublicKey(TWPublicKeyTypeSECP256k1);
+    const auto pubKey3 = key3.getPublicKey(TWPublicKeyTypeSECP256k1);
+    // 2-of-3
+    Data redeemScriptData{OP_2, 33};
+    append(redeemScriptData, key1.getPublicKey(TWPublicKeyTypeSECP256k1).bytes);
+    redeemScriptData.push_back(33);
+    append(redeemScriptData, pubKey2.bytes);
+    redeemScriptData.push_back(33);
+    append(redeemScriptData, pubKey3.bytes);
+    redeemScriptData.push_back(OP_3);
+    redeemScriptData.push_back(OP_CHECKMULTISIG);
+    const auto redeemScript = Script(redeemScriptData);
+
+    SigningInput input;
+    input.hashType = hashTypeForCoin(TWCoinTypeBitcoin);
+    input.amount = 50'000;
+    input.byteFee = 1;
+    input.toAddress = "1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx";
+    input.changeAddress = "1FQc5LdgGHMHEN9nwkjmz6tWkxhPpxBvBU";
+    input.coinType = TWCoinTypeBitcoin;
+    input.scripts[hex(redeemScript.hash())] = redeemScript;
+    // only the 2nd and 3rd keys are held locally
+    input.privateKeys.push_back(key2);
+    input.privateKeys.push_back(key3);
+
+    UTXO utxo;
+    utxo.script = Script::buildPayToWitnessScriptHash(Hash::sha256(redeemScript.bytes));
+    utxo.amount = 100'000;
+    utxo.outPoint = OutPoint(parse_hex("0001000000000000000000000000000000000000000000000000000000000000"), 0, UINT32_MAX);
+    input.utxos.push_back(utxo);
+
+    auto result = TransactionSigner<Transaction, TransactionBuilder>::sign(input);
+    ASSERT_TRUE(result) << std::to_string(result.error());
+    const auto signedTx = result.payload();
+
+    // <empty> <signature 2> <signature 3> <witness script>, signatures in public key order
+    const auto& witness = signedTx.inputs[0].scriptWitness;
+    ASSERT_EQ(witness.size(), 4ul);
+    EXPECT_TRUE(witness[0].empty());
+    EXPECT_EQ(hex(witness[3]), hex(redeemScript.bytes));
+    const auto sighash = signedTx.getSignatureHash(redeemScript, 0, TWBitcoinSigHashTypeAll, utxo.amount, WITNESS_V0);
+    EXPECT_TRUE(pubKey2.verifyAsDER(subData(witness[1], 0, witness[1].size() - 1), sighash));
+    EXPECT_TRUE(pubKey3.verifyAsDER(subData(witness[2], 0, witness[2].size() - 1), sighash));
+
+    // not enough keys
+    input.privateKeys.pop_back();
+    result = TransactionSigner<Transaction, TransactionBuilder>::sign(input);
+    ASSERT_FALSE(result);
+    EXPECT_EQ(result.error(), Common::Proto::Error_missing_private_key);
+}
+
 TEST(BitcoinSigning, EncodeP2WPKH) {
     auto unsignedTx = Transaction(1, 0x11);
 
@@ -0,0 +1,113 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+#include "Bitcoin/Script.h"
+#include "Hash.h"
+#include "HexCoding.h"
+#include "PrivateKey.h"
+#include "proto/Bitcoin.pb.h"
+#include "TestUtilities.h"
+
+#include <TrustWalletCore/TWAnySigner.h>
+#include <TrustWalletCore/TWBitcoinSigHashType.h>
+#include <TrustWalletCore/TWBitcoinSplitPayment.h>
+
+#include <gtest/gtest.h>
+
+namespace TW::Bitcoin {
+
+namespace {
+
+void addUtxo(Proto::SigningInput& input, const std::string& privateKey, const std::string& hash, int64_t amount) {
+    const auto key = PrivateKey(parse_hex(privateKey));
+    const auto pubKey = key.getPublicKey(TWPublicKeyTypeSECP256k1);
+    const auto script = Script::buildPayToV0WitnessProgram(Hash::sha256ripemd(pubKey.bytes.data(), pubKey.bytes.size()));
+    input.add_private_key(key.bytes.data(), key.bytes.size());
+
+    auto& utxo = *input.add_utxo();
+    const auto hashData = parse_hex(hash);
+    utxo.mutable_out_point()->set_hash(hashData.data(), hashData.size());
+    utxo.mutable_out_point()->set_index(0);
+    utxo.mutable_out_point()->set_sequence(UINT32_MAX);
+    utxo.set_script(script.bytes.data(), script.bytes.size());
+    utxo.set_amount(amount);
+}
+
+Proto::SplitPaymentPlans planSplitPayment(const Proto::SigningInput& input) {
+    const auto inputData = data(input.SerializeAsString());
+    const auto outputData = WRAPD(TWBitcoinSplitPaymentPlan(WRAPD(TWDataCreateWithData(&inputData)).get()));
+    Proto::SplitPaymentPlans output;
+    output.ParseFromArray(TWDataBytes(outputData.get()), static_cast<int>(TWDataSize(outputData.get())));
+    return output;
+}
+
+} // namespace
+
+TEST(TWBitcoinSplitPayment, PlanAndSign) {
+    Proto::SigningInput input;
+    input.set_hash_type(TWBitcoinSigHashTypeAll);
+    input.set_amount(80'000);
+    input.set_byte_fee(1);
+    input.set_to_address("1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx");
+    input.set_coin_type(TWCoinTypeBitcoin);
+    input.add_split_change_addresses("1FQc5LdgGHMHEN9nwkjmz6tWkxhPpxBvBU");
+    input.add_split_change_addresses("19cAJn4Ms8jodBBGtroBNNpCZiHAWGAq7X");
+    addUtxo(input, "bbc27228ddcb9209d7fd6f36b02f7dfa6252af40bb2f1cbc7a557da8027ff866", "fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f", 60'000);
+    addUtxo(input, "619c335025c7f4012e556c2a58b2506e30b8511b53ade95ea316fd8c3286feb9", "ef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a", 50'000);
+
+    const auto plans = planSplitPayment(input);
+
+    EXPECT_EQ(plans.error(), Common::Proto::OK);
+    ASSERT_EQ(plans.plans_size(), 2);
+    ASSERT_EQ(plans.change_addresses_size(), 2);
+    EXPECT_EQ(plans.plans(0).amount(), 60'000 - 113);
+    EXPECT_EQ(plans.plans(0).change(), 0);
+    EXPECT_EQ(plans.plans(1).amount(), 80'000 - (60'000 - 113));
+    EXPECT_EQ(plans.plans(1).change(), 50'000 - (80'000 - (60'000 - 113)) - 147);
+    EXPECT_EQ(plans.change_addresses(0), "1FQc5LdgGHMHEN9nwkjmz6tWkxhPpxBvBU");
+    EXPECT_EQ(plans.change_addresses(1), "19cAJn4Ms8jodBBGtroBNNpCZiHAWGAq7X");
+
+    // each transaction is signed separately, with its own plan and change address
+    for (auto i = 0; i < plans.plans_size(); ++i) {
+        auto signingInput = input;
+        *signingInput.mutable_plan() = plans.plans(i);
+        signingInput.set_amount(plans.plans(i).amount());
+        signingInput.set_change_address(plans.change_addresses(i));
+
+        Proto::SigningOutput output;
+        ANY_SIGN(signingInput, TWCoinTypeBitcoin);
+
+        EXPECT_EQ(output.error(), Common::Proto::OK);
+        ASSERT_EQ(output.transaction().inputs_size(), 1);
+        ASSERT_EQ(output.transaction().outputs_size(), i == 0 ? 1 : 2);
+        EXPECT_EQ(output.transaction().outputs(0).value(), plans.plans(i).amount());
+    }
+}
+
+TEST(TWBitcoinSplitPayment, MissingChangeAddresses) {
+    Proto::SigningInput input;
+    input.set_hash_type(TWBitcoinSigHashTypeAll);
+    input.set_amount(80'000);
+    input.set_byte_fee(1);
+    input.set_to_address("1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx");
+    input.set_change_address("1FQc5LdgGHMHEN9nwkjmz6tWkxhPpxBvBU");
+    input.set_coin_type(TWCoinTypeBitcoin);
+    addUtxo(input, "bbc27228ddcb9209d7fd6f36b02f7dfa6252af40bb2f1cbc7a557da8027ff866", "fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f", 60'000);
+    addUtxo(input, "619c335025c7f4012e556c2a58b2506e30b8511b53ade95ea316fd8c3286feb9", "ef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a", 50'000);
+
+    const auto plans = planSplitPayment(input);
+
+    EXPECT_EQ(plans.error(), Common::Proto::Error_invalid_params);
+    EXPECT_EQ(plans.plans_size(), 0);
+}
+
+TEST(TWBitcoinSplitPayment, InvalidInput) {
+    const auto inputData = DATA("ff");
+    const auto outputData = WRAPD(TWBitcoinSplitPaymentPlan(inputData.get()));
+    EXPECT_EQ(TWDataSize(outputData.get()), 0ul);
+}
+
+} // namespace TW::Bitcoin
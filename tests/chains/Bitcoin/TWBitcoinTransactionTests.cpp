@@ -4,6 +4,7 @@
 // terms governing use, modification, and redistribution, is contained in the
 // file LICENSE at the root of the source code distribution tree.
 
+#include "Bitcoin/Script.h"
 #include "Bitcoin/Transaction.h"
 #include "HexCoding.h"
 
@@ -36,4 +37,127 @@ TEST(BitcoinTransaction, Encode) {
               "02000000035897de6bd6027a475eadd57019d4e6872c396d0716c4875a5f1a6fcfdf385c1f0000000000ffffffffbf829c6bcf84579331337659d31f89dfd138f7f7785802d5501c92333145ca7c1200000000ffffffff22a6f904655d53ae2ff70e701a0bbd90aa3975c0f40bfc6cc996a9049e31cdfc0100000000ffffffff0280a81201000000001976a9141fc11f39be1729bf973a7ab6a615ca4729d6457488ac0084d717000000001976a914f2d4db28cad6502226ee484ae24505c2885cb12d88ac00000000");
 }
 
+TEST(BitcoinTransaction, CheckTemplateVerifyHash) {
+    auto transaction = Transaction(2, 0);
+    transaction.inputs.emplace_back(OutPoint(parse_hex("5897de6bd6027a475eadd57019d4e6872c396d0716c4875a5f1a6fcfdf385c1f"), 0), Script(), 4294967295);
+    transaction.inputs.emplace_back(OutPoint(parse_hex("bf829c6bcf84579331337659d31f89dfd138f7f7785802d5501c92333145ca7c"), 18), Script(), 4294967295);
+    transaction.inputs.emplace_back(OutPoint(parse_hex("22a6f904655d53ae2ff70e701a0bbd90aa3975c0f40bfc6cc996a9049e31cdfc"), 1), Script(), 4294967295);
+    transaction.outputs.emplace_back(18000000, Script(parse_hex("76a9141fc11f39be1729bf973a7ab6a615ca4729d6457488ac")));
+    transaction.outputs.emplace_back(400000000, Script(parse_hex("76a914f2d4db28cad6502226ee484ae24505c2885cb12d88ac")));
+
+    const auto hash0 = transaction.getCheckTemplateVerifyHash(0);
+    EXPECT_EQ(hex(hash0), "f87f605d128cdc69d59bf1cd0a28f88adeb4ca03c0e630cf7402f87d297416b6");
+    // input index is committed to, but not the outpoints
+    EXPECT_EQ(hex(transaction.getCheckTemplateVerifyHash(1)), "e28dbcceef9244c78f42dc76a18d2c8d405d268231b48efb0ba66b686efb06b3");
+    transaction.inputs[0].previousOutput.index = 5;
+    EXPECT_EQ(hex(transaction.getCheckTemplateVerifyHash(0)), hex(hash0));
+
+    const auto script = Script::buildCheckTemplateVerify(hash0);
+    EXPECT_EQ(hex(script.bytes), "20" + hex(hash0) + "b3");
+}
+
+TEST(BitcoinTransaction, LockTimes) {
+    auto transaction = Transaction(2, 850'000);
+    transaction.inputs.emplace_back(OutPoint(parse_hex("5897de6bd6027a475eadd57019d4e6872c396d0716c4875a5f1a6fcfdf385c1f"), 0), Script(), 0xfffffffd);
+    transaction.inputs.emplace_back(OutPoint(parse_hex("bf829c6bcf84579331337659d31f89dfd138f7f7785802d5501c92333145ca7c"), 18), Script(), 144);
+    transaction.inputs.emplace_back(OutPoint(parse_hex("22a6f904655d53ae2ff70e701a0bbd90aa3975c0f40bfc6cc996a9049e31cdfc"), 1), Script(), 0x004000a9);
+
+    const auto descriptions = transaction.describeLockTimes();
+    ASSERT_EQ(descriptions.size(), 3ul);
+    EXPECT_EQ(descriptions[0], "not valid before block 850000");
+    EXPECT_EQ(descriptions[1], "input 1 requires 144-block relative age");
+    EXPECT_EQ(descriptions[2], "input 2 requires 86528-second relative age");
+
+    EXPECT_FALSE(transaction.isLockTimeSatisfied(850'000, 0));
+    EXPECT_TRUE(transaction.isLockTimeSatisfied(850'001, 0));
+
+    // time based
+    transaction.lockTime = 1'700'000'000;
+    EXPECT_EQ(transaction.describeLockTimes()[0], "not valid before time 1700000000");
+    EXPECT_FALSE(transaction.isLockTimeSatisfied(900'000, 1'700'000'000));
+    EXPECT_TRUE(transaction.isLockTimeSatisfied(900'000, 1'700'000'001));
+
+    // version 1: relative lock-times are not enforced
+    transaction._version = 1;
+    EXPECT_EQ(transaction.describeLockTimes().size(), 1ul);
+
+    // all inputs final: lock-time is not enforced
+    for (auto& input : transaction.inputs) {
+        input.sequence = 0xffffffff;
+    }
+    EXPECT_TRUE(transaction.describeLockTimes().empty());
+    EXPECT_TRUE(transaction.isLockTimeSatisfied(0, 0));
+}
+
+TEST(BitcoinTransaction, TxidAndMalleability) {
+    auto transaction = Transaction(2, 0);
+    transaction.inputs.emplace_back(OutPoint(parse_hex("5897de6bd6027a475eadd57019d4e6872c396d0716c4875a5f1a6fcfdf385c1f"), 0), Script(), 4294967295);
+    transaction.inputs.emplace_back(OutPoint(parse_hex("bf829c6bcf84579331337659d31f89dfd138f7f7785802d5501c92333145ca7c"), 18), Script(), 4294967295);
+    transaction.inputs.emplace_back(OutPoint(parse_hex("22a6f904655d53ae2ff70e701a0bbd90aa3975c0f40bfc6cc996a9049e31cdfc"), 1), Script(), 4294967295);
+    transaction.outputs.emplace_back(18000000, Script(parse_hex("76a9141fc11f39be1729bf973a7ab6a615ca4729d6457488ac")));
+    transaction.outputs.emplace_back(400000000, Script(parse_hex("76a914f2d4db28cad6502226ee484ae24505c2885cb12d88ac")));
+
+    const auto txid = "4cceeb002452e7600d380b96a4f45f8969817ebe8b812ca4cae00473208d8f1e";
+    EXPECT_EQ(hex(transaction.txid()), txid);
+    EXPECT_EQ(hex(transaction.wtxid()), txid);
+
+    transaction.inputs[0].scriptWitness = {parse_hex("aabbcc"), parse_hex("0102")};
+    EXPECT_EQ(hex(transaction.txid()), txid);
+    EXPECT_EQ(hex(transaction.wtxid()), "630399e3e5e1973b4b4e50fceeb0f02f6e2a3b0ccc7247c6bb2f8ca8913de552");
+
+    // witness changed: same transaction
+    auto other = transaction;
+    other.inputs[0].scriptWitness = {parse_hex("ddeeff")};
+    EXPECT_EQ(hex(other.wtxid()), "27d3e288d308edc0738ee2e504d7333ebc4c50b2975000eca0564b3d38c8ba8c");
+    EXPECT_TRUE(transaction.isSameIgnoringWitness(other));
+    other.outputs[0].value += 1;
+    EXPECT_FALSE(transaction.isSameIgnoringWitness(other));
+
+    EXPECT_FALSE(transaction.hasMalleableScriptSig());
+    // minimal pushes: signature and public key
+    transaction.inputs[1].script = Script(parse_hex("03aabbcc" "21" "02a4f53fdc7f1c4d8ab2dc7b6a1c76de5bcb2bd8d9a70c2d1aeb00c5d59ee2d8d1"));
+    EXPECT_FALSE(transaction.hasMalleableScriptSig());
+    // non-push opcode
+    transaction.inputs[1].script = Script(parse_hex("03aabbcc76"));
+    EXPECT_TRUE(transaction.hasMalleableScriptSig());
+
+    EXPECT_TRUE(Script(parse_hex("00514f")).isPushOnlyMinimal());
+    EXPECT_TRUE(Script(parse_hex("0100")).isPushOnlyMinimal());
+    EXPECT_FALSE(Script(parse_hex("0101")).isPushOnlyMinimal());    // should be OP_1
+    EXPECT_FALSE(Script(parse_hex("0181")).isPushOnlyMinimal());    // should be OP_1NEGATE
+    EXPECT_FALSE(Script(parse_hex("4c03aabbcc")).isPushOnlyMinimal()); // needless OP_PUSHDATA1
+    EXPECT_FALSE(Script(parse_hex("03aabb")).isPushOnlyMinimal());  // truncated
+}
+
+TEST(BitcoinTransaction, SigHashCache) {
+    auto transaction = Transaction(2, 0);
+    transaction.inputs.emplace_back(OutPoint(parse_hex("5897de6bd6027a475eadd57019d4e6872c396d0716c4875a5f1a6fcfdf385c1f"), 0), Script(), 4294967295);
+    transaction.inputs.emplace_back(OutPoint(parse_hex("bf829c6bcf84579331337659d31f89dfd138f7f7785802d5501c92333145ca7c"), 18), Script(), 4294967294);
+    transaction.outputs.emplace_back(18000000, Script(parse_hex("76a9141fc11f39be1729bf973a7ab6a615ca4729d6457488ac")));
+    transaction.outputs.emplace_back(400000000, Script(parse_hex("76a914f2d4db28cad6502226ee484ae24505c2885cb12d88ac")));
+    const auto scriptCode = Script(parse_hex("76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac"));
+
+    const auto sigHashes = [&](const Transaction& tx) {
+        std::vector<Data> hashes;
+        for (auto hashType : {TWBitcoinSigHashTypeAll, TWBitcoinSigHashTypeSingle, TWBitcoinSigHashTypeAnyoneCanPay}) {
+            for (auto i = 0ul; i < tx.inputs.size(); ++i) {
+                hashes.push_back(tx.getSignatureHash(scriptCode, i, hashType, 1000, WITNESS_V0));
+            }
+        }
+        return hashes;
+    };
+    const auto expected = sigHashes(transaction);
+
+    transaction.cacheSigHashes();
+    EXPECT_EQ(sigHashes(transaction), expected);
+    // signing changes the input scripts only
+    transaction.inputs[0].script = Script(parse_hex("03aabbcc"));
+    transaction.inputs[0].scriptWitness = {parse_hex("aabbcc")};
+    EXPECT_EQ(sigHashes(transaction), expected);
+
+    transaction.outputs[0].value += 1;
+    transaction.clearSigHashCache();
+    EXPECT_NE(sigHashes(transaction), expected);
+}
+
 } // namespace TW::Bitcoin
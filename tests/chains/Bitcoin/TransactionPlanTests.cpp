@@ -687,4 +687,660 @@ TEST(TransactionPlan, OpReturn) {
     EXPECT_EQ(feeCalculator.calculate(1, 3, byteFee), 205 * byteFee);
 }
 
+TEST(TransactionPlan, SubtractFeeFromAmount) {
+    auto utxos = buildTestUTXOs({100'000});
+    auto sigingInput = buildSigningInput(50'000, 1, utxos);
+    sigingInput.subtractFeeFromAmount = true;
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    // fee is deducted from the amount, change is unaffected
+    EXPECT_TRUE(verifyPlan(txPlan, {100'000}, 50'000 - 147, 147));
+    EXPECT_EQ(txPlan.change, 50'000);
+}
+
+TEST(TransactionPlan, SubtractFeeFromAmountNoRoomForFee) {
+    // amount plus fee would not fit, but amount alone does
+    auto utxos = buildTestUTXOs({100'000});
+    auto sigingInput = buildSigningInput(99'900, 1, utxos);
+    sigingInput.subtractFeeFromAmount = true;
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    // the change of 100 would be dust, it goes to the recipient, without the fee for the change output
+    EXPECT_TRUE(verifyPlan(txPlan, {100'000}, 100'000 - 113, 113));
+    EXPECT_EQ(txPlan.change, 0);
+}
+
+TEST(TransactionPlan, SubtractFeeFromAmountDust) {
+    auto utxos = buildTestUTXOs({100'000});
+    auto sigingInput = buildSigningInput(200, 1, utxos);
+    sigingInput.subtractFeeFromAmount = true;
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    // 200 - 147 is below the dust limit
+    EXPECT_TRUE(verifyPlan(txPlan, {}, 0, 0, Common::Proto::Error_dust_amount_requested));
+
+    // 700 - 147 is above the P2PKH dust limit of 546
+    sigingInput.amount = 700;
+    txPlan = TransactionBuilder::plan(sigingInput);
+    EXPECT_TRUE(verifyPlan(txPlan, {100'000}, 700 - 147, 147));
+}
+
+TEST(TransactionPlan, DustThreshold) {
+    EXPECT_EQ(TransactionBuilder::dustThreshold(Script::lockScriptForAddress("1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx", TWCoinTypeBitcoin)), 546);
+    EXPECT_EQ(TransactionBuilder::dustThreshold(Script::lockScriptForAddress("bc1q7s0a2l4aguksehx8hf93hs9yggl6njxds6m02g", TWCoinTypeBitcoin)), 294);
+    EXPECT_EQ(TransactionBuilder::dustThreshold(Script::lockScriptForAddress("3QJmV3qfvL9SuYo34YihAf3sRCW3qSinyC", TWCoinTypeBitcoin)), 540);
+    EXPECT_EQ(TransactionBuilder::dustThreshold(Script::buildOpReturnScript(parse_hex("01020304"))), 0);
+}
+
+TEST(TransactionPlan, SplitChange) {
+    auto utxos = buildTestUTXOs({100'000});
+    auto sigingInput = buildSigningInput(50'000, 1, utxos);
+    sigingInput.changeOutputCount = 2;
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    // one more output, +34 bytes
+    EXPECT_TRUE(verifyPlan(txPlan, {100'000}, 50'000, 147 + 34));
+    EXPECT_EQ(txPlan.changeOutputCount, 2u);
+
+    auto txResult = TransactionBuilder::build<Transaction>(txPlan, sigingInput.toAddress, sigingInput.changeAddress, TWCoinTypeBitcoin, 0);
+    ASSERT_TRUE(txResult);
+    const auto tx = txResult.payload();
+    ASSERT_EQ(tx.outputs.size(), 3ul);
+    EXPECT_EQ(tx.outputs[0].value, 50'000);
+    EXPECT_EQ(tx.outputs[1].value, 24'910);
+    EXPECT_EQ(tx.outputs[2].value, 24'909);
+    EXPECT_EQ(tx.outputs[1].script, tx.outputs[2].script);
+}
+
+TEST(TransactionPlan, SplitChangeReducedToAvoidDust) {
+    auto utxos = buildTestUTXOs({100'000});
+    auto sigingInput = buildSigningInput(97'000, 1, utxos);
+    sigingInput.changeOutputCount = 10;
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    // 10 parts would be below the P2PKH dust limit of 546, only 4 are used, and the fee is for 5 outputs
+    EXPECT_TRUE(verifyPlan(txPlan, {100'000}, 97'000, 249));
+    EXPECT_EQ(txPlan.changeOutputCount, 4u);
+    EXPECT_EQ(txPlan.change, 2'751);
+
+    auto txResult = TransactionBuilder::build<Transaction>(txPlan, sigingInput.toAddress, sigingInput.changeAddress, TWCoinTypeBitcoin, 0);
+    ASSERT_TRUE(txResult);
+    const auto tx = txResult.payload();
+    ASSERT_EQ(tx.outputs.size(), 5ul);
+    for (auto i = 1ul; i < tx.outputs.size(); ++i) {
+        EXPECT_GE(tx.outputs[i].value, TransactionBuilder::dustThreshold(tx.outputs[i].script));
+    }
+}
+
+TEST(TransactionPlan, SplitChangeRandomized) {
+    auto utxos = buildTestUTXOs({100'000});
+    auto sigingInput = buildSigningInput(50'000, 1, utxos);
+    sigingInput.changeOutputCount = 3;
+    sigingInput.randomizeChangeAmounts = true;
+    sigingInput.randomSeed = 7;
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    EXPECT_TRUE(verifyPlan(txPlan, {100'000}, 50'000, 215));
+    EXPECT_EQ(txPlan.change, 49'785);
+    EXPECT_EQ(txPlan.changeAmounts, (std::vector<Amount>{24'708, 21'851, 3'226}));
+
+    auto txResult = TransactionBuilder::build<Transaction>(txPlan, sigingInput.toAddress, sigingInput.changeAddress, TWCoinTypeBitcoin, 0);
+    ASSERT_TRUE(txResult);
+    const auto tx = txResult.payload();
+    ASSERT_EQ(tx.outputs.size(), 4ul);
+    EXPECT_EQ(tx.outputs[1].value, 24'708);
+    EXPECT_EQ(tx.outputs[2].value, 21'851);
+    EXPECT_EQ(tx.outputs[3].value, 3'226);
+
+    // amounts not matching the change are rejected
+    txPlan.changeAmounts[0] += 1;
+    EXPECT_EQ(TransactionBuilder::build<Transaction>(txPlan, sigingInput.toAddress, sigingInput.changeAddress, TWCoinTypeBitcoin, 0).error(),
+              Common::Proto::Error_invalid_params);
+}
+
+TEST(TransactionPlan, AddressReuseReported) {
+    auto utxos = buildTestUTXOs({100'000});
+    auto sigingInput = buildSigningInput(50'000, 1, utxos);
+    sigingInput.usedAddresses = {"bc1q7s0a2l4aguksehx8hf93hs9yggl6njxds6m02g", "1FQc5LdgGHMHEN9nwkjmz6tWkxhPpxBvBU"};
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    // reuse is only reported, plan is not affected
+    EXPECT_TRUE(verifyPlan(txPlan, {100'000}, 50'000, 147));
+    ASSERT_EQ(txPlan.reusedAddresses.size(), 1ul);
+    EXPECT_EQ(txPlan.reusedAddresses[0], "1FQc5LdgGHMHEN9nwkjmz6tWkxhPpxBvBU");
+}
+
+TEST(TransactionPlan, AddressReuseRejected) {
+    auto utxos = buildTestUTXOs({100'000});
+    auto sigingInput = buildSigningInput(50'000, 1, utxos);
+    sigingInput.usedAddresses = {"1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx"};
+    sigingInput.rejectAddressReuse = true;
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    EXPECT_TRUE(verifyPlan(txPlan, {}, 0, 0, Common::Proto::Error_address_reused));
+    ASSERT_EQ(txPlan.reusedAddresses.size(), 1ul);
+    EXPECT_EQ(txPlan.reusedAddresses[0], "1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx");
+}
+
+TEST(TransactionPlan, AddressReuseNone) {
+    auto utxos = buildTestUTXOs({100'000});
+    auto sigingInput = buildSigningInput(50'000, 1, utxos);
+    sigingInput.usedAddresses = {"bc1q7s0a2l4aguksehx8hf93hs9yggl6njxds6m02g"};
+    sigingInput.rejectAddressReuse = true;
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    EXPECT_TRUE(verifyPlan(txPlan, {100'000}, 50'000, 147));
+    EXPECT_TRUE(txPlan.reusedAddresses.empty());
+}
+
+TEST(TransactionPlan, SplitPaymentByAddress) {
+    auto utxos = buildTestUTXOs({30'000, 30'000});
+    auto otherUtxo = buildTestUTXO(50'000);
+    otherUtxo.script = Script(parse_hex("0014769bdff96a02f9135a1d19b749db6a78fe07dc90"));
+    utxos.push_back(otherUtxo);
+    auto sigingInput = buildSigningInput(80'000, 1, utxos);
+    sigingInput.splitChangeAddresses = {"1FQc5LdgGHMHEN9nwkjmz6tWkxhPpxBvBU", "19cAJn4Ms8jodBBGtroBNNpCZiHAWGAq7X"};
+
+    auto plans = TransactionBuilder::planSplitPayment(sigingInput);
+
+    ASSERT_EQ(plans.size(), 2ul);
+    // larger group first, fully spent
+    EXPECT_TRUE(verifyPlan(plans[0], {30'000, 30'000}, 60'000 - 181, 181));
+    // remainder from the other address, with change
+    EXPECT_TRUE(verifyPlan(plans[1], {50'000}, 80'000 - (60'000 - 181), 147));
+    EXPECT_EQ(plans[0].amount + plans[1].amount, 80'000);
+    EXPECT_EQ(TransactionBuilder::splitPaymentChangeAddress(sigingInput, 0), "1FQc5LdgGHMHEN9nwkjmz6tWkxhPpxBvBU");
+    EXPECT_EQ(TransactionBuilder::splitPaymentChangeAddress(sigingInput, 1), "19cAJn4Ms8jodBBGtroBNNpCZiHAWGAq7X");
+
+    // a shared change address would link the transactions
+    sigingInput.splitChangeAddresses.clear();
+    plans = TransactionBuilder::planSplitPayment(sigingInput);
+    ASSERT_EQ(plans.size(), 1ul);
+    EXPECT_TRUE(verifyPlan(plans[0], {}, 0, 0, Common::Proto::Error_invalid_params));
+
+    // too few change addresses
+    sigingInput.splitChangeAddresses = {"1FQc5LdgGHMHEN9nwkjmz6tWkxhPpxBvBU"};
+    plans = TransactionBuilder::planSplitPayment(sigingInput);
+    ASSERT_EQ(plans.size(), 1ul);
+    EXPECT_TRUE(verifyPlan(plans[0], {}, 0, 0, Common::Proto::Error_invalid_params));
+}
+
+TEST(TransactionPlan, SplitPaymentNoDustPart) {
+    auto utxos = buildTestUTXOs({30'000, 30'000});
+    auto otherUtxo = buildTestUTXO(50'000);
+    otherUtxo.script = Script(parse_hex("0014769bdff96a02f9135a1d19b749db6a78fe07dc90"));
+    utxos.push_back(otherUtxo);
+    // the first group could pay all but 300, which would be dust
+    auto sigingInput = buildSigningInput(60'000 - 181 + 300, 1, utxos);
+    sigingInput.splitChangeAddresses = {"1FQc5LdgGHMHEN9nwkjmz6tWkxhPpxBvBU", "19cAJn4Ms8jodBBGtroBNNpCZiHAWGAq7X"};
+
+    auto plans = TransactionBuilder::planSplitPayment(sigingInput);
+
+    ASSERT_EQ(plans.size(), 2ul);
+    // the first part is reduced, the second one is the dust limit
+    EXPECT_TRUE(verifyPlan(plans[0], {30'000, 30'000}, 60'000 - 181 + 300 - 546, 215));
+    EXPECT_TRUE(verifyPlan(plans[1], {50'000}, 546, 147));
+    EXPECT_EQ(plans[0].amount + plans[1].amount, 60'000 - 181 + 300);
+}
+
+TEST(TransactionPlan, SplitPaymentSingleGroup) {
+    auto utxos = buildTestUTXOs({30'000, 30'000});
+    auto sigingInput = buildSigningInput(20'000, 1, utxos);
+
+    auto plans = TransactionBuilder::planSplitPayment(sigingInput);
+
+    ASSERT_EQ(plans.size(), 1ul);
+    EXPECT_TRUE(verifyPlan(plans[0], {30'000}, 20'000, 147));
+}
+
+TEST(TransactionPlan, SplitPaymentInsufficient) {
+    auto utxos = buildTestUTXOs({30'000, 30'000});
+    auto otherUtxo = buildTestUTXO(50'000);
+    otherUtxo.script = Script(parse_hex("0014769bdff96a02f9135a1d19b749db6a78fe07dc90"));
+    utxos.push_back(otherUtxo);
+    auto sigingInput = buildSigningInput(200'000, 1, utxos);
+    sigingInput.splitChangeAddresses = {"1FQc5LdgGHMHEN9nwkjmz6tWkxhPpxBvBU", "19cAJn4Ms8jodBBGtroBNNpCZiHAWGAq7X"};
+
+    auto plans = TransactionBuilder::planSplitPayment(sigingInput);
+
+    ASSERT_EQ(plans.size(), 1ul);
+    EXPECT_TRUE(verifyPlan(plans[0], {}, 0, 0, Common::Proto::Error_not_enough_utxos));
+}
+
+TEST(TransactionPlan, SplitPaymentSubtractFee) {
+    auto utxos = buildTestUTXOs({30'000, 30'000});
+    auto sigingInput = buildSigningInput(20'000, 1, utxos);
+    sigingInput.subtractFeeFromAmount = true;
+
+    auto plans = TransactionBuilder::planSplitPayment(sigingInput);
+
+    ASSERT_EQ(plans.size(), 1ul);
+    EXPECT_TRUE(verifyPlan(plans[0], {}, 0, 0, Common::Proto::Error_invalid_params));
+}
+
+TEST(TransactionPlan, SplitPaymentOpReturn) {
+    auto utxos = buildTestUTXOs({30'000, 30'000});
+    auto otherUtxo = buildTestUTXO(50'000);
+    otherUtxo.script = Script(parse_hex("0014769bdff96a02f9135a1d19b749db6a78fe07dc90"));
+    utxos.push_back(otherUtxo);
+    auto sigingInput = buildSigningInput(80'000, 1, utxos);
+    sigingInput.splitChangeAddresses = {"19cAJn4Ms8jodBBGtroBNNpCZiHAWGAq7X", "1FQc5LdgGHMHEN9nwkjmz6tWkxhPpxBvBU"};
+    sigingInput.outputOpReturn = data("SWAP:THOR.RUNE:thor1tpercamkkxec0q0jk6ltdnlqvsw29guap8wmcl:");
+
+    auto plans = TransactionBuilder::planSplitPayment(sigingInput);
+
+    // not split, the OP_RETURN is in one transaction only
+    ASSERT_EQ(plans.size(), 1ul);
+    EXPECT_EQ(plans[0].error, Common::Proto::OK);
+    EXPECT_EQ(plans[0].amount, 80'000);
+    EXPECT_EQ(plans[0].outputOpReturn, sigingInput.outputOpReturn);
+    EXPECT_EQ(plans[0].utxos.size(), 3ul);
+
+    sigingInput.outputOpReturn.clear();
+    sigingInput.extraOpReturns = {data("memo")};
+    plans = TransactionBuilder::planSplitPayment(sigingInput);
+    ASSERT_EQ(plans.size(), 1ul);
+    EXPECT_EQ(plans[0].error, Common::Proto::OK);
+    EXPECT_EQ(plans[0].extraOpReturns.size(), 1ul);
+
+    // still no shared change address
+    sigingInput.splitChangeAddresses.clear();
+    sigingInput.changeAddress = "";
+    plans = TransactionBuilder::planSplitPayment(sigingInput);
+    ASSERT_EQ(plans.size(), 1ul);
+    EXPECT_TRUE(verifyPlan(plans[0], {}, 0, 0, Common::Proto::Error_invalid_params));
+}
+
+TEST(TransactionPlan, MinConfirmations) {
+    auto utxos = buildTestUTXOs({20'000, 80'000});
+    utxos[0].confirmations = 1;
+    utxos[1].confirmations = 6;
+    auto sigingInput = buildSigningInput(15'000, 1, utxos);
+    sigingInput.minConfirmations = 3;
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    // the smaller one would be chosen, but it is too fresh
+    EXPECT_TRUE(verifyPlan(txPlan, {80'000}, 15'000, 147));
+}
+
+TEST(TransactionPlan, MinConfirmationsNoneEligible) {
+    auto utxos = buildTestUTXOs({20'000, 80'000});
+    auto sigingInput = buildSigningInput(15'000, 1, utxos);
+    sigingInput.minConfirmations = 1;
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    EXPECT_TRUE(verifyPlan(txPlan, {}, 0, 0, Common::Proto::Error_not_enough_utxos));
+}
+
+TEST(TransactionPlan, PreferOldestUtxos) {
+    auto utxos = buildTestUTXOs({80'000, 20'000, 30'000});
+    utxos[0].confirmations = 1;
+    utxos[1].confirmations = 10;
+    utxos[2].confirmations = 5;
+    auto sigingInput = buildSigningInput(40'000, 1, utxos);
+    sigingInput.preferOldestUtxos = true;
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    // oldest ones first, even though the single 80'000 one would suffice
+    EXPECT_TRUE(verifyPlan(txPlan, {20'000, 30'000}, 40'000, 215));
+}
+
+TEST(TransactionPlan, BatchPayment) {
+    auto utxos = buildTestUTXOs({100'000});
+    auto sigingInput = buildSigningInput(30'000, 1, utxos);
+    sigingInput.extraOutputs = {{"bc1q7s0a2l4aguksehx8hf93hs9yggl6njxds6m02g", 20'000}};
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    // one more P2WPKH output, +31 bytes
+    EXPECT_EQ(txPlan.error, Common::Proto::OK);
+    EXPECT_EQ(txPlan.availableAmount, 100'000);
+    EXPECT_EQ(txPlan.amount, 30'000);
+    EXPECT_EQ(txPlan.fee, 147 + 31);
+    EXPECT_EQ(txPlan.change, 49'822);
+
+    auto txResult = TransactionBuilder::build<Transaction>(txPlan, sigingInput.toAddress, sigingInput.changeAddress, TWCoinTypeBitcoin, 0);
+    ASSERT_TRUE(txResult);
+    const auto tx = txResult.payload();
+    ASSERT_EQ(tx.outputs.size(), 3ul);
+    EXPECT_EQ(tx.outputs[0].value, 30'000);
+    EXPECT_EQ(tx.outputs[1].value, 20'000);
+    EXPECT_EQ(hex(tx.outputs[1].script.bytes), "0014f41fd57ebd472d0cdcc7ba4b1bc0a4423fa9c8cd");
+    EXPECT_EQ(tx.outputs[2].value, 49'822);
+}
+
+TEST(TransactionPlan, BatchPaymentMaxAmount) {
+    auto utxos = buildTestUTXOs({100'000});
+    auto sigingInput = buildSigningInput(30'000, 1, utxos, true);
+    sigingInput.extraOutputs = {{"bc1q7s0a2l4aguksehx8hf93hs9yggl6njxds6m02g", 20'000}};
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    // the main output takes the rest, no change
+    EXPECT_EQ(txPlan.error, Common::Proto::OK);
+    EXPECT_EQ(txPlan.fee, 113 + 31);
+    EXPECT_EQ(txPlan.amount, 100'000 - 20'000 - 144);
+    EXPECT_EQ(txPlan.change, 0);
+}
+
+TEST(TransactionPlan, BatchPaymentInvalidOutput) {
+    auto utxos = buildTestUTXOs({100'000});
+    auto sigingInput = buildSigningInput(30'000, 1, utxos);
+
+    sigingInput.extraOutputs = {{"bc1q7s0a2l4aguksehx8hf93hs9yggl6njxds6m02g", 20'000}, {"invalid", 20'000}};
+    EXPECT_EQ(TransactionBuilder::plan(sigingInput).error, Common::Proto::Error_invalid_address);
+
+    sigingInput.extraOutputs = {{"bc1q7s0a2l4aguksehx8hf93hs9yggl6njxds6m02g", 100}};
+    EXPECT_EQ(TransactionBuilder::plan(sigingInput).error, Common::Proto::Error_dust_amount_requested);
+
+    // relay dust limit of a P2WPKH output is 294
+    sigingInput.extraOutputs = {{"bc1q7s0a2l4aguksehx8hf93hs9yggl6njxds6m02g", 293}};
+    EXPECT_EQ(TransactionBuilder::plan(sigingInput).error, Common::Proto::Error_dust_amount_requested);
+    sigingInput.extraOutputs = {{"bc1q7s0a2l4aguksehx8hf93hs9yggl6njxds6m02g", 294}};
+    EXPECT_EQ(TransactionBuilder::plan(sigingInput).error, Common::Proto::OK);
+
+    // for a P2PKH output it is 546
+    sigingInput.extraOutputs = {{"1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx", 545}};
+    EXPECT_EQ(TransactionBuilder::plan(sigingInput).error, Common::Proto::Error_dust_amount_requested);
+}
+
+TEST(TransactionPlan, BatchPaymentInsufficient) {
+    auto utxos = buildTestUTXOs({10'000});
+    auto sigingInput = buildSigningInput(5'000, 1, utxos);
+    sigingInput.extraOutputs = {{"bc1q7s0a2l4aguksehx8hf93hs9yggl6njxds6m02g", 20'000}};
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    EXPECT_TRUE(verifyPlan(txPlan, {}, 0, 0, Common::Proto::Error_not_enough_utxos));
+}
+
+TEST(TransactionPlan, FutureWitnessVersionDestination) {
+    auto utxos = buildTestUTXOs({100'000});
+    auto sigingInput = buildSigningInput(50'000, 1, utxos);
+    sigingInput.toAddress = "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs";
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    // allowed, but reported; output is 7 bytes shorter than P2PKH
+    EXPECT_TRUE(verifyPlan(txPlan, {100'000}, 50'000, 140));
+    ASSERT_EQ(txPlan.futureWitnessAddresses.size(), 1ul);
+    EXPECT_EQ(txPlan.futureWitnessAddresses[0], "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs");
+
+    sigingInput.toAddress = "bc1q7s0a2l4aguksehx8hf93hs9yggl6njxds6m02g";
+    EXPECT_TRUE(TransactionBuilder::plan(sigingInput).futureWitnessAddresses.empty());
+}
+
+TEST(TransactionPlan, OpReturnPolicy) {
+    auto utxos = buildTestUTXOs({100'000});
+    auto sigingInput = buildSigningInput(50'000, 1, utxos);
+    sigingInput.outputOpReturn = Data(100, 0x01);
+    sigingInput.extraOpReturns = {Data(4, 0x02)};
+
+    // 100 bytes is over the default limit
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+    auto txResult = TransactionBuilder::build<Transaction>(txPlan, sigingInput.toAddress, sigingInput.changeAddress, TWCoinTypeBitcoin, 0);
+    ASSERT_FALSE(txResult);
+    EXPECT_EQ(txResult.error(), Common::Proto::Error_invalid_memo);
+
+    sigingInput.maxOpReturnLength = 200;
+    txPlan = TransactionBuilder::plan(sigingInput);
+    EXPECT_EQ(txPlan.error, Common::Proto::OK);
+    EXPECT_EQ(txPlan.extraOpReturns.size(), 1ul);
+    EXPECT_EQ(txPlan.maxOpReturnLength, 200u);
+
+    txResult = TransactionBuilder::build<Transaction>(txPlan, sigingInput.toAddress, sigingInput.changeAddress, TWCoinTypeBitcoin, 0);
+    ASSERT_TRUE(txResult);
+    const auto tx = txResult.payload();
+    ASSERT_EQ(tx.outputs.size(), 4ul);
+    EXPECT_EQ(tx.outputs[2].value, 0);
+    EXPECT_EQ(hex(tx.outputs[2].script.bytes), "6a4c64" + hex(Data(100, 0x01)));
+    EXPECT_EQ(tx.outputs[3].value, 0);
+    EXPECT_EQ(hex(tx.outputs[3].script.bytes), "6a0402020202");
+}
+
+TEST(TransactionPlan, OpReturnSizeNonSegwit) {
+    auto utxos = buildTestUTXOs({100'000});
+    auto sigingInput = buildSigningInput(50'000, 1, utxos, false, TWCoinTypeDogecoin);
+
+    // 3 outputs of 34 bytes
+    EXPECT_TRUE(verifyPlan(TransactionBuilder::plan(sigingInput), {100'000}, 50'000, 260));
+
+    // 80 bytes: the OP_RETURN output is 92 bytes
+    sigingInput.outputOpReturn = Data(80, 0x01);
+    EXPECT_TRUE(verifyPlan(TransactionBuilder::plan(sigingInput), {100'000}, 50'000, 260 + 58));
+
+    // 200 bytes: 212 bytes
+    sigingInput.outputOpReturn = Data(200, 0x01);
+    sigingInput.maxOpReturnLength = 200;
+    EXPECT_TRUE(verifyPlan(TransactionBuilder::plan(sigingInput), {100'000}, 50'000, 260 + 178));
+}
+
+TEST(TransactionPlan, RandomizedSelection) {
+    auto utxos = buildTestUTXOs({30'000, 40'000, 30'000, 40'000});
+    utxos[1].script = Script(parse_hex("0014769bdff96a02f9135a1d19b749db6a78fe07dc90"));
+    utxos[3].script = utxos[1].script;
+    auto sigingInput = buildSigningInput(50'000, 1, utxos);
+    sigingInput.randomizeUtxoSelection = true;
+    sigingInput.randomSeed = 42;
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    // one address group is drawn, both of its UTXOs are needed
+    EXPECT_EQ(txPlan.error, Common::Proto::OK);
+    ASSERT_EQ(txPlan.utxos.size(), 2ul);
+    EXPECT_EQ(txPlan.utxos[0].script, txPlan.utxos[1].script);
+    EXPECT_EQ(txPlan.amount, 50'000);
+    EXPECT_EQ(txPlan.fee, 215);
+
+    // reproducible with the same seed
+    for (auto i = 0; i < 5; ++i) {
+        auto txPlan2 = TransactionBuilder::plan(sigingInput);
+        ASSERT_EQ(txPlan2.utxos.size(), 2ul);
+        EXPECT_EQ(txPlan2.utxos[0].script, txPlan.utxos[0].script);
+        EXPECT_EQ(txPlan2.availableAmount, txPlan.availableAmount);
+    }
+
+    // one UTXO would cover the amount, but its address group is spent as a whole
+    sigingInput.amount = 20'000;
+    txPlan = TransactionBuilder::plan(sigingInput);
+    EXPECT_EQ(txPlan.error, Common::Proto::OK);
+    ASSERT_EQ(txPlan.utxos.size(), 2ul);
+    EXPECT_EQ(txPlan.utxos[0].script, txPlan.utxos[1].script);
+    EXPECT_EQ(txPlan.availableAmount, 70'000);
+    EXPECT_EQ(txPlan.amount, 20'000);
+}
+
+TEST(TransactionPlan, RandomizedSelectionOrderIsPortable) {
+    // one UTXO per address, the order of the selection follows the seeded shuffle
+    auto utxos = buildTestUTXOs({50'000, 60'000, 70'000, 80'000});
+    for (auto i = 0ul; i < utxos.size(); ++i) {
+        utxos[i].script = Script(parse_hex("0014" + std::string(39, '0') + std::to_string(i)));
+    }
+    auto sigingInput = buildSigningInput(10'000, 1, utxos);
+    sigingInput.randomizeUtxoSelection = true;
+    sigingInput.randomSeed = 42;
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+    ASSERT_EQ(txPlan.error, Common::Proto::OK);
+    ASSERT_EQ(txPlan.utxos.size(), 1ul);
+    EXPECT_EQ(txPlan.utxos[0].amount, 60'000);
+}
+
+TEST(TransactionPlan, MempoolChainLimits) {
+    auto utxos = buildTestUTXOs({80'000, 20'000, 30'000, 40'000});
+    // too long unconfirmed chains
+    utxos[0].ancestorCount = 25;
+    utxos[1].ancestorCount = 3;
+    utxos[1].ancestorSize = 101'000;
+    // within limits
+    utxos[2].ancestorCount = 24;
+    utxos[2].ancestorSize = 100'000;
+    auto sigingInput = buildSigningInput(50'000, 1, utxos);
+
+    auto txPlan = TransactionBuilder::plan(sigingInput);
+
+    EXPECT_TRUE(verifyPlan(txPlan, {30'000, 40'000}, 50'000, 215));
+}
+
+TEST(TransactionPlan, MempoolChainLimitsCombined) {
+    auto utxos = buildTestUTXOs({50'000, 60'000, 35'000, 45'000});
+    // two unconfirmed UTXOs, each within the limits, but from different parents
+    for (auto i = 0ul; i < utxos.size(); ++i) {
+        utxos[i].outPoint.hash[0] = static_cast<byte>(i + 1);
+    }
+    utxos[0].ancestorCount = 15;
+    utxos[0].ancestorSize = 3'000;
+    utxos[1].ancestorCount = 15;
+    utxos[1].ancestorSize = 3'000;
+
+    {
+        // together with the new transaction 31 ancestors, above 25: only the confirmed ones are used
+        auto txPlan = TransactionBuilder::plan(buildSigningInput(60'000, 1, utxos));
+        EXPECT_TRUE(verifyPlan(txPlan, {35'000, 45'000}, 60'000, 215));
+    }
+    {
+        // outputs of the same parent share its ancestors
+        auto sameParent = utxos;
+        sameParent[1].outPoint.hash = sameParent[0].outPoint.hash;
+        auto txPlan = TransactionBuilder::plan(buildSigningInput(60'000, 1, sameParent));
+        EXPECT_TRUE(verifyPlan(txPlan, {50'000, 60'000}, 60'000, 215));
+    }
+    {
+        // too large together
+        auto large = utxos;
+        large[0].ancestorCount = 2;
+        large[0].ancestorSize = 60'000;
+        large[1].ancestorCount = 2;
+        large[1].ancestorSize = 41'000;
+        auto txPlan = TransactionBuilder::plan(buildSigningInput(60'000, 1, large));
+        EXPECT_TRUE(verifyPlan(txPlan, {35'000, 45'000}, 60'000, 215));
+    }
+    {
+        // not enough confirmed UTXOs
+        auto fewConfirmed = utxos;
+        fewConfirmed.pop_back();
+        auto txPlan = TransactionBuilder::plan(buildSigningInput(60'000, 1, fewConfirmed));
+        EXPECT_TRUE(verifyPlan(txPlan, {}, 0, 0, Common::Proto::Error_mempool_chain_limit));
+    }
+}
+
+TEST(TransactionPlan, CoinbaseMaturity) {
+    auto utxos = buildTestUTXOs({80'000, 30'000, 40'000});
+    utxos[0].isCoinbase = true;
+    utxos[0].confirmations = 99;
+    utxos[1].isCoinbase = true;
+    utxos[1].confirmations = 100;
+    utxos[2].confirmations = 1;
+
+    {
+        // immature coinbase UTXO is not used
+        auto sigingInput = buildSigningInput(50'000, 1, utxos);
+        auto txPlan = TransactionBuilder::plan(sigingInput);
+        EXPECT_TRUE(verifyPlan(txPlan, {30'000, 40'000}, 50'000, 215));
+    }
+    {
+        // only immature coinbase UTXOs
+        auto sigingInput = buildSigningInput(50'000, 1, UTXOs(utxos[0]));
+        auto txPlan = TransactionBuilder::plan(sigingInput);
+        EXPECT_TRUE(verifyPlan(txPlan, {}, 0, 0, Common::Proto::Error_immature_coinbase));
+    }
+}
+
+TEST(TransactionPlan, TransactionVersion) {
+    auto utxos = buildTestUTXOs({100'000});
+    auto sigingInput = buildSigningInput(50'000, 1, utxos);
+    {
+        // default
+        auto txPlan = TransactionBuilder::plan(sigingInput);
+        EXPECT_TRUE(verifyPlan(txPlan, {100'000}, 50'000, 147));
+        EXPECT_EQ(txPlan.version, 0);
+        auto txResult = TransactionBuilder::build<Transaction>(txPlan, sigingInput.toAddress, sigingInput.changeAddress, TWCoinTypeBitcoin, 0);
+        ASSERT_TRUE(txResult);
+        EXPECT_EQ(txResult.payload()._version, 1);
+    }
+    {
+        sigingInput.version = 2;
+        auto txPlan = TransactionBuilder::plan(sigingInput);
+        EXPECT_TRUE(verifyPlan(txPlan, {100'000}, 50'000, 147));
+        EXPECT_EQ(txPlan.version, 2);
+        auto txResult = TransactionBuilder::build<Transaction>(txPlan, sigingInput.toAddress, sigingInput.changeAddress, TWCoinTypeBitcoin, 0);
+        ASSERT_TRUE(txResult);
+        EXPECT_EQ(txResult.payload()._version, 2);
+
+        // the same plan is not valid for Zcash
+        const auto zcashResult = TransactionBuilder::build<Transaction>(txPlan, sigingInput.toAddress, sigingInput.changeAddress, TWCoinTypeZcash, 0);
+        ASSERT_FALSE(zcashResult);
+        EXPECT_EQ(zcashResult.error(), Common::Proto::Error_invalid_params);
+    }
+    {
+        sigingInput.version = 4;
+        auto txPlan = TransactionBuilder::plan(sigingInput);
+        EXPECT_TRUE(verifyPlan(txPlan, {}, 0, 0, Common::Proto::Error_invalid_params));
+    }
+
+    EXPECT_TRUE(TransactionBuilder::isValidVersion(3, TWCoinTypeBitcoin));
+    EXPECT_TRUE(TransactionBuilder::isValidVersion(3, TWCoinTypeDash));
+    EXPECT_FALSE(TransactionBuilder::isValidVersion(3, TWCoinTypeLitecoin));
+    EXPECT_FALSE(TransactionBuilder::isValidVersion(-1, TWCoinTypeBitcoin));
+    EXPECT_TRUE(TransactionBuilder::isValidVersion(0, TWCoinTypeZcash));
+    EXPECT_FALSE(TransactionBuilder::isValidVersion(4, TWCoinTypeZcash));
+}
+
+TEST(TransactionPlan, LockTimeNotReached) {
+    auto utxos = buildTestUTXOs({80'000, 30'000, 40'000});
+    utxos[0].outPoint.sequence = 144; // relative lock of 144 blocks
+    utxos[0].confirmations = 143;
+    utxos[1].outPoint.sequence = 144;
+    utxos[1].confirmations = 144;
+    utxos[2].confirmations = 1;
+
+    {
+        // relative lock-time is not enforced for version 1
+        auto sigingInput = buildSigningInput(50'000, 1, utxos);
+        auto txPlan = TransactionBuilder::plan(sigingInput);
+        EXPECT_TRUE(verifyPlan(txPlan, {80'000}, 50'000, 147));
+    }
+    {
+        // locked UTXO is not used
+        auto sigingInput = buildSigningInput(50'000, 1, utxos);
+        sigingInput.version = 2;
+        auto txPlan = TransactionBuilder::plan(sigingInput);
+        EXPECT_TRUE(verifyPlan(txPlan, {30'000, 40'000}, 50'000, 215));
+    }
+    {
+        // only locked UTXOs
+        auto sigingInput = buildSigningInput(50'000, 1, UTXOs(utxos[0]));
+        sigingInput.version = 2;
+        auto txPlan = TransactionBuilder::plan(sigingInput);
+        EXPECT_TRUE(verifyPlan(txPlan, {}, 0, 0, Common::Proto::Error_lock_time_not_reached));
+    }
+    {
+        // absolute lock-time about a week ahead is accepted, further ahead is rejected
+        auto sigingInput = buildSigningInput(50'000, 1, utxos);
+        sigingInput.blockHeight = 800'000;
+        sigingInput.lockTime = 800'000 + TransactionBuilder::MaxLockTimeAheadBlocks;
+        EXPECT_TRUE(verifyPlan(TransactionBuilder::plan(sigingInput), {80'000}, 50'000, 147));
+        sigingInput.lockTime += 1;
+        EXPECT_TRUE(verifyPlan(TransactionBuilder::plan(sigingInput), {}, 0, 0, Common::Proto::Error_lock_time_not_reached));
+        // time based lock-time, or unknown block height: not checked
+        sigingInput.lockTime = 1'900'000'000;
+        EXPECT_TRUE(verifyPlan(TransactionBuilder::plan(sigingInput), {80'000}, 50'000, 147));
+        sigingInput.lockTime = 900'000;
+        sigingInput.blockHeight = 0;
+        EXPECT_TRUE(verifyPlan(TransactionBuilder::plan(sigingInput), {80'000}, 50'000, 147));
+    }
+}
+
 } // namespace TW::Bitcoin
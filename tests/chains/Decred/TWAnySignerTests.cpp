@@ -107,6 +107,53 @@ TEST(TWAnySignerDecred, PlanAndSign) {
     ASSERT_EQ(hex(output.encoded()), "0100000001fdbfe9dd703f306794a467f175be5bd9748a7925033ea1cf9889d7cf4dd1155000000000000000000002809698000000000000001976a914989b1aecabf1c24e213cc0f2d8a22ffee25dd4e188ace23bc8010000000000001976a9142a194fc92e27fef9cc2b057bc9060c580cbb484888ac000000000000000001000000000000000000000000ffffffff6a47304402203e6ee9e16d6bc36bb4242f7a4cac333a1c2a150ea16143412b88b721f6ae16bf02201019affdf815a5c22e4b0fb7e4685c4707094922d6a41354f9055d3bb0f26e630121026cc34b92cefb3a4537b3edb0b6044c04af27c01583c577823ecc69a9a21119b6");
 }
 
+TEST(TWAnySignerDecred, PlanAndSignExtraOutputs) {
+    auto input = createInput();
+    auto& extra = *input.add_extra_outputs();
+    extra.set_to_address("DsUoWCAxprdGNtKQqambFbTcSBgH1SHn9Gp");
+    extra.set_amount(1000000);
+    input.set_change_output_count(2);
+
+    Bitcoin::Proto::TransactionPlan plan;
+    ANY_PLAN(input, plan, TWCoinTypeDecred);
+
+    EXPECT_EQ(plan.error(), Common::Proto::OK);
+    EXPECT_EQ(plan.amount(), 10000000);
+    EXPECT_EQ(plan.fee(), 330); // 4 outputs
+    EXPECT_EQ(plan.change(), 39900000 - 10000000 - 1000000 - 330);
+
+    *input.mutable_plan() = plan;
+    Proto::SigningOutput output;
+    ANY_SIGN(input, TWCoinTypeDecred);
+
+    ASSERT_EQ(output.error(), Common::Proto::OK);
+    ASSERT_EQ(output.transaction().outputs_size(), 4);
+    EXPECT_EQ(output.transaction().outputs(0).value(), 10000000);
+    EXPECT_EQ(output.transaction().outputs(1).value(), 1000000);
+    EXPECT_EQ(output.transaction().outputs(2).value() + output.transaction().outputs(3).value(), plan.change());
+}
+
+TEST(TWAnySignerDecred, PlanUnsupportedOptions) {
+    {
+        auto input = createInput();
+        input.set_version(2);
+        Bitcoin::Proto::TransactionPlan plan;
+        ANY_PLAN(input, plan, TWCoinTypeDecred);
+        EXPECT_EQ(plan.error(), Common::Proto::Error_invalid_params);
+
+        Proto::SigningOutput output;
+        ANY_SIGN(input, TWCoinTypeDecred);
+        EXPECT_EQ(output.error(), Common::Proto::Error_invalid_params);
+    }
+    {
+        auto input = createInput();
+        input.add_extra_op_returns("memo");
+        Bitcoin::Proto::TransactionPlan plan;
+        ANY_PLAN(input, plan, TWCoinTypeDecred);
+        EXPECT_EQ(plan.error(), Common::Proto::Error_invalid_params);
+    }
+}
+
 TEST(TWAnySignerDecred, SupportsJSON) {
     ASSERT_FALSE(TWAnySignerSupportsJSON(TWCoinTypeDecred));
 }
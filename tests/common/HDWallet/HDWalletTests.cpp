@@ -7,6 +7,7 @@
 #include "Base58.h"
 #include "Bitcoin/Address.h"
 #include "Bitcoin/CashAddress.h"
+#include "Bitcoin/Script.h"
 #include "Bitcoin/SegwitAddress.h"
 #include "IoTeX/Address.h"
 #include "Cosmos/Address.h"
@@ -407,6 +408,76 @@ TEST(HDWallet, Derive_XpubPub_vs_PrivPub) {
     }
 }
 
+TEST(HDWallet, getPublicKeysFromExtended) {
+    const auto zpub = "zpub6rNUNtxSa9Gxvm4Bdxf1MPMwrvkzwDx6vP96Hkzw3jiQKdg3fhXBStxjn12YixQB8h88B3RMSRscRstf9AEVaYr3MAqVBEWBDuEJU4PGaT9";
+    const auto coin = TWCoinTypeBitcoin;
+
+    const auto publicKeys = HDWallet<>::getPublicKeysFromExtended(zpub, coin, 0, 0, 3);
+    ASSERT_EQ(publicKeys.size(), 3ul);
+    EXPECT_EQ(hex(publicKeys[0].bytes), "02df9ef2a7a5552765178b181e1e1afdefc7849985c7dfe9647706dd4fa40df6ac");
+    EXPECT_EQ(hex(publicKeys[2].bytes), "031e1f64d2f6768dccb6814545b2e2d58e26ad5f91b7cbaffe881ed572c65060db");
+    EXPECT_EQ(Bitcoin::SegwitAddress(publicKeys[0], "bc").string(), "bc1qpsp72plnsqe6e2dvtsetxtww2cz36ztmfxghpd");
+
+    // same as one-by-one derivation, also for the change branch and a non-zero start index
+    const auto changeKeys = HDWallet<>::getPublicKeysFromExtended(zpub, coin, 1, 5, 10);
+    ASSERT_EQ(changeKeys.size(), 10ul);
+    for (auto i = 0u; i < changeKeys.size(); ++i) {
+        const auto path = DerivationPath("m/84'/0'/0'/1/" + std::to_string(5 + i));
+        const auto publicKey = HDWallet<>::getPublicKeyFromExtended(zpub, coin, path);
+        ASSERT_TRUE(publicKey.has_value());
+        EXPECT_EQ(hex(changeKeys[i].bytes), hex(publicKey->bytes));
+    }
+
+    EXPECT_TRUE(HDWallet<>::getPublicKeysFromExtended(zpub, coin, 0, 0, 0).empty());
+    EXPECT_TRUE(HDWallet<>::getPublicKeysFromExtended("invalid", coin, 0, 0, 3).empty());
+    // limits: count, hardened indices
+    EXPECT_TRUE(HDWallet<>::getPublicKeysFromExtended(zpub, coin, 0, 0, HDWallet<>::maxPublicKeysFromExtended + 1).empty());
+    EXPECT_TRUE(HDWallet<>::getPublicKeysFromExtended(zpub, coin, 0x80000000, 0, 3).empty());
+    EXPECT_TRUE(HDWallet<>::getPublicKeysFromExtended(zpub, coin, 0, 0x7ffffffe, 3).empty());
+    EXPECT_EQ(HDWallet<>::getPublicKeysFromExtended(zpub, coin, 0, 0x7ffffffe, 2).size(), 2ul);
+}
+
+TEST(HDWallet, getPublicKeysFromExtendedParallel) {
+    const auto zpub = "zpub6rNUNtxSa9Gxvm4Bdxf1MPMwrvkzwDx6vP96Hkzw3jiQKdg3fhXBStxjn12YixQB8h88B3RMSRscRstf9AEVaYr3MAqVBEWBDuEJU4PGaT9";
+    const auto coin = TWCoinTypeBitcoin;
+
+    // large enough to be split among threads
+    const auto publicKeys = HDWallet<>::getPublicKeysFromExtended(zpub, coin, 0, 0, 1000);
+    ASSERT_EQ(publicKeys.size(), 1000ul);
+    EXPECT_EQ(hex(publicKeys[0].bytes), "02df9ef2a7a5552765178b181e1e1afdefc7849985c7dfe9647706dd4fa40df6ac");
+    for (auto i : {1u, 127u, 128u, 255u, 256u, 500u, 999u}) {
+        const auto publicKey = HDWallet<>::getPublicKeyFromExtended(zpub, coin, DerivationPath("m/84'/0'/0'/0/" + std::to_string(i)));
+        ASSERT_TRUE(publicKey.has_value());
+        EXPECT_EQ(hex(publicKeys[i].bytes), hex(publicKey->bytes));
+    }
+}
+
+TEST(HDWallet, getLockScriptsFromExtended) {
+    const auto zpub = "zpub6rNUNtxSa9Gxvm4Bdxf1MPMwrvkzwDx6vP96Hkzw3jiQKdg3fhXBStxjn12YixQB8h88B3RMSRscRstf9AEVaYr3MAqVBEWBDuEJU4PGaT9";
+    const auto coin = TWCoinTypeBitcoin;
+
+    const auto scripts = HDWallet<>::getLockScriptsFromExtended(zpub, coin, TWDerivationBitcoinSegwit, 0, 0, 3);
+    ASSERT_EQ(scripts.size(), 3ul);
+    EXPECT_EQ(hex(scripts[0]), hex(Bitcoin::Script::lockScriptForAddress("bc1qpsp72plnsqe6e2dvtsetxtww2cz36ztmfxghpd", coin).bytes));
+
+    // the same keys as legacy P2PKH addresses
+    const auto publicKeys = HDWallet<>::getPublicKeysFromExtended(zpub, coin, 0, 0, 3);
+    const auto legacyScripts = HDWallet<>::getLockScriptsFromExtended(zpub, coin, TWDerivationBitcoinLegacy, 0, 0, 3);
+    ASSERT_EQ(legacyScripts.size(), 3ul);
+    EXPECT_EQ(hex(legacyScripts[2]), hex(Bitcoin::Script::buildPayToPublicKeyHash(publicKeys[2].hash({})).bytes));
+
+    // many, on multiple threads
+    const auto manyScripts = HDWallet<>::getLockScriptsFromExtended(zpub, coin, TWDerivationBitcoinSegwit, 0, 0, 1000);
+    ASSERT_EQ(manyScripts.size(), 1000ul);
+    EXPECT_EQ(hex(manyScripts[0]), hex(scripts[0]));
+    EXPECT_EQ(hex(manyScripts[2]), hex(scripts[2]));
+
+    EXPECT_TRUE(HDWallet<>::getLockScriptsFromExtended("invalid", coin, TWDerivationBitcoinSegwit, 0, 0, 3).empty());
+    EXPECT_TRUE(HDWallet<>::getLockScriptsFromExtended(zpub, coin, TWDerivationBitcoinSegwit, 0, 0, HDWallet<>::maxPublicKeysFromExtended + 1).empty());
+    // no Bitcoin-style scripts
+    EXPECT_TRUE(HDWallet<>::getLockScriptsFromExtended(zpub, TWCoinTypeEthereum, TWDerivationDefault, 0, 0, 3).empty());
+}
+
 TEST(HDWallet, getKeyByCurve) {
     const auto derivPath = "m/44'/539'/0'/0/0";
     HDWallet wallet = HDWallet(mnemonic1, "");
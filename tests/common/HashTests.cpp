@@ -65,6 +65,11 @@ TEST(HashTests, hmac256) {
     EXPECT_EQ(hex(hmac), expectedHmac);
 }
 
+TEST(HashTests, sha256Tagged) {
+    EXPECT_EQ(hex(Hash::sha256Tagged("BIP0340/challenge", {})), "c216d352f5818b7b4beacd4ae0a26fe888080823d2a598856661bcd54f1b3713");
+    EXPECT_EQ(hex(Hash::sha256Tagged("TapLeaf", TW::data(brownFox))), "6eeb8287cc9464810b61d55fe85ce71b2725a4bc6cec06819de793e6e0b2322d");
+}
+
 TEST(HashTests, allHashEnum) {
     const auto tests = {
         make_tuple(Hash::HasherSha1, "2fd4e1c67a2d28fced849ee1bb76e7391b93eb12"),
@@ -17,6 +17,7 @@
 #include "Bitcoin/Script.h"
 #include "Bitcoin/SegwitAddress.h"
 
+#include "Hash.h"
 #include "HexCoding.h"
 #include "PrivateKey.h"
 #include "PublicKey.h"
@@ -240,6 +241,13 @@ TEST(TransactionCompiler, BitcoinCompileWithSignatures) {
     EXPECT_EQ(hex(preSigningOutput.hash_public_keys()[1].public_key_hash()), hex(inPubKeyHash0));
     EXPECT_EQ(hex(preSigningOutput.hash_public_keys()[2].public_key_hash()), hex(inPubKeyHash0));
 
+    // The pre-images are exported as well, for signers which do the hashing themselves
+    for (const auto& h: preSigningOutput.hash_public_keys()) {
+        EXPECT_EQ(h.preimage().size(), 182ul);
+        const auto preImage = data(h.preimage());
+        EXPECT_EQ(hex(Hash::sha256d(preImage.data(), preImage.size())), hex(h.data_hash()));
+    }
+
     // Simulate signatures, normally they are obtained from external source, e.g. a signature server.
     std::vector<Data> signatureVec;
     std::vector<Data> pubkeyVec;
@@ -8,8 +8,10 @@
 
 #include "Coin.h"
 
+#include <TrustWalletCore/TWBitcoinScript.h>
 #include <TrustWalletCore/TWHash.h>
 #include <TrustWalletCore/TWData.h>
+#include <TrustWalletCore/TWDataVector.h>
 #include <TrustWalletCore/TWHDWallet.h>
 #include <TrustWalletCore/TWMnemonic.h>
 #include <TrustWalletCore/TWPrivateKey.h>
@@ -418,6 +420,38 @@ TEST(HDWallet, PublicKeyFromZ) {
     assertStringsEqual(address4, "bc1qm97vqzgj934vnaq9s53ynkyf9dgr05rargr04n");
 }
 
+TEST(HDWallet, PublicKeysFromExtended) {
+    auto zpub = STRING("zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs");
+    auto publicKeys = WRAP(TWDataVector, TWHDWalletGetPublicKeysFromExtended(zpub.get(), TWCoinTypeBitcoin, 0, 4, 8));
+
+    ASSERT_EQ(TWDataVectorSize(publicKeys.get()), 8ul);
+    assertHexEqual(WRAPD(TWDataVectorGet(publicKeys.get(), 0)), "03995137c8eb3b223c904259e9b571a8939a0ec99b0717684c3936407ca8538c1b");
+    assertHexEqual(WRAPD(TWDataVectorGet(publicKeys.get(), 7)), "0226a07edd0227fa6bc36239c0bd4db83d5e488f8fb1eeb68f89a5be916aad2d60");
+
+    auto tooMany = WRAP(TWDataVector, TWHDWalletGetPublicKeysFromExtended(zpub.get(), TWCoinTypeBitcoin, 0, 0, 10001));
+    EXPECT_EQ(TWDataVectorSize(tooMany.get()), 0ul);
+    auto invalid = WRAP(TWDataVector, TWHDWalletGetPublicKeysFromExtended(STRING("xpub0000").get(), TWCoinTypeBitcoin, 0, 0, 8));
+    EXPECT_EQ(TWDataVectorSize(invalid.get()), 0ul);
+}
+
+TEST(HDWallet, LockScriptsFromExtended) {
+    auto zpub = STRING("zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs");
+    auto scripts = WRAP(TWDataVector, TWHDWalletGetLockScriptsFromExtended(zpub.get(), TWCoinTypeBitcoin, TWDerivationBitcoinSegwit, 0, 4, 8));
+    auto publicKeys = WRAP(TWDataVector, TWHDWalletGetPublicKeysFromExtended(zpub.get(), TWCoinTypeBitcoin, 0, 4, 8));
+
+    ASSERT_EQ(TWDataVectorSize(scripts.get()), 8ul);
+    // script of the address of the public key
+    auto publicKey = WRAP(TWPublicKey, TWPublicKeyCreateWithData(WRAPD(TWDataVectorGet(publicKeys.get(), 7)).get(), TWPublicKeyTypeSECP256k1));
+    auto address = WRAPS(TWCoinTypeDeriveAddressFromPublicKey(TWCoinTypeBitcoin, publicKey.get()));
+    auto script = WRAP(TWBitcoinScript, TWBitcoinScriptLockScriptForAddress(address.get(), TWCoinTypeBitcoin));
+    auto scriptData = WRAPD(TWBitcoinScriptData(script.get()));
+    EXPECT_EQ(TWDataSize(scriptData.get()), 22ul);
+    EXPECT_TRUE(TWDataEqual(WRAPD(TWDataVectorGet(scripts.get(), 7)).get(), scriptData.get()));
+
+    auto invalid = WRAP(TWDataVector, TWHDWalletGetLockScriptsFromExtended(STRING("xpub0000").get(), TWCoinTypeBitcoin, TWDerivationBitcoinSegwit, 0, 0, 8));
+    EXPECT_EQ(TWDataVectorSize(invalid.get()), 0ul);
+}
+
 TEST(HDWallet, PublicKeyFromExtended_Ethereum) {
     const auto xpub = STRING("xpub6C7LtZJgtz1BKXG9mExKUxYvX7HSF38UMMmGbpqNQw3DfYwAw8E6sH7VSVxFipvEEm2afSqTjoRgcLmycXX4zfxCWJ4HY73a9KdgvfHEQGB");
     const auto xpubAddr = WRAP(TWPublicKey, TWHDWalletGetPublicKeyFromExtended(xpub.get(), TWCoinTypeEthereum, STRING("m/44'/60'/0'/0/1").get()));
@@ -45,6 +45,19 @@ TEST(TWHashTests, Sha256) {
     }
 }
 
+TEST(TWHashTests, Sha256Tagged) {
+    auto tests = {
+        make_tuple(string("TapLeaf"), string(""), string("5212c288a377d1f8164962a5a13429f9ba6a7b84e59776a52c6637df2106facb")),
+        make_tuple(string("BIP0340/challenge"), brownFox, string("d15bde4827853672c5ca26f539056bcc63a48ac6370baaa36b2af4da42f9fedf")),
+    };
+    for (auto &test: tests) {
+        const auto tag = STRING(get<0>(test).c_str());
+        const auto inData = WRAPD(TWDataCreateWithBytes(reinterpret_cast<const uint8_t *>(get<1>(test).c_str()), get<1>(test).length()));
+        const auto hash = WRAPD(TWHashSHA256Tagged(tag.get(), inData.get()));
+        EXPECT_EQ(hex(data(TWDataBytes(hash.get()), TWDataSize(hash.get()))), get<2>(test));
+    }
+}
+
 TEST(TWHashTests, Sha512) {
     auto tests = {
         make_tuple(string(""), string("cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e")),